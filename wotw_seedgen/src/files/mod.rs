@@ -127,6 +127,20 @@ mod fs_access {
         Ok(headers)
     }
 
+    /// Find all header tests in the current directory and "headers" subdirectory
+    ///
+    /// See [`HeaderTest`](crate::header::HeaderTest) for the test syntax
+    pub fn find_header_tests() -> Result<Vec<PathBuf>, String> {
+        let mut current_directory = PathBuf::from(".");
+        let mut tests = files_in_directory(&current_directory, "wotwrht")?;
+        current_directory.push(HEADER_FOLDER);
+        if let Ok(mut more) = files_in_directory(&current_directory, "wotwrht") {
+            tests.append(&mut more);
+        }
+
+        Ok(tests)
+    }
+
     fn with_extension(identifier: &str, extension: impl AsRef<OsStr>) -> PathBuf {
         Path::new(identifier).with_extension(extension)
     }
@@ -0,0 +1,412 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use rand::distributions::WeightedError;
+
+use crate::{languages::ParseErrorCollection, Item};
+
+/// Error returned by [`generate_seed`](super::generate_seed)
+#[derive(Debug, Clone)]
+pub enum GenerationError {
+    /// The provided settings are invalid
+    Settings(SettingsError),
+    /// A header could not be read, parsed or applied
+    Header(HeaderError),
+    /// The logic doesn't support what the settings request
+    Logic(LogicError),
+    /// An attempt at placing items failed
+    Placement(PlacementError),
+    /// Every attempt at placing items failed
+    RetriesExhausted {
+        /// The causes of the individual attempts failing, in order
+        attempts: Vec<GenerationError>,
+    },
+    /// The [`GenerationObserver`](super::GenerationObserver) requested to stop generating
+    Cancelled,
+}
+impl Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GenerationError::Settings(err) => write!(f, "{err}"),
+            GenerationError::Header(err) => write!(f, "{err}"),
+            GenerationError::Logic(err) => write!(f, "{err}"),
+            GenerationError::Placement(err) => write!(f, "{err}"),
+            GenerationError::RetriesExhausted { attempts } => write!(
+                f,
+                "All {} attempts to generate a seed failed :(",
+                attempts.len()
+            ),
+            GenerationError::Cancelled => write!(f, "Generation was cancelled"),
+        }
+    }
+}
+impl Error for GenerationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GenerationError::Settings(err) => Some(err),
+            GenerationError::Header(err) => Some(err),
+            GenerationError::Logic(err) => Some(err),
+            GenerationError::Placement(err) => Some(err),
+            GenerationError::RetriesExhausted { attempts } => {
+                attempts.last().map(|err| err as &(dyn Error + 'static))
+            }
+            GenerationError::Cancelled => None,
+        }
+    }
+}
+impl From<SettingsError> for GenerationError {
+    fn from(err: SettingsError) -> GenerationError {
+        GenerationError::Settings(err)
+    }
+}
+impl From<HeaderError> for GenerationError {
+    fn from(err: HeaderError) -> GenerationError {
+        GenerationError::Header(err)
+    }
+}
+impl From<LogicError> for GenerationError {
+    fn from(err: LogicError) -> GenerationError {
+        GenerationError::Logic(err)
+    }
+}
+impl From<PlacementError> for GenerationError {
+    fn from(err: PlacementError) -> GenerationError {
+        GenerationError::Placement(err)
+    }
+}
+
+/// Invalid [`UniverseSettings`](crate::settings::UniverseSettings)
+#[derive(Debug, Clone)]
+pub enum SettingsError {
+    /// An inline header used a name that is already used by a file header
+    AmbiguousHeaderName { world_index: usize, name: String },
+    /// The same header parameter was configured with different values
+    ConflictingHeaderConfig {
+        world_index: usize,
+        header_name: String,
+        config_name: String,
+        values: (String, String),
+    },
+    /// The configured spawn doesn't exist in the logic
+    SpawnNotFound { world_index: usize, spawn: String },
+    /// The configured spawn exists, but cannot be spawned on
+    InvalidSpawn { world_index: usize, spawn: String },
+    /// The spawn should have been chosen randomly, but no candidates exist
+    NoSpawnLocations { world_index: usize },
+    /// The [`GenerationTuning`](crate::settings::GenerationTuning) contains unusable values
+    InvalidTuning { message: String },
+    /// [`UniverseSettings::door_randomization`](crate::settings::UniverseSettings::door_randomization) was set without [`GenerationTuning::experimental_doors`](crate::settings::GenerationTuning::experimental_doors)
+    ExperimentalDoors,
+    /// A [`PlacementConstraint`](crate::settings::PlacementConstraint) refers to a node that doesn't exist in the logic
+    UnknownConstraintLocation {
+        world_index: usize,
+        identifier: String,
+    },
+    /// A [`PlacementConstraint`](crate::settings::PlacementConstraint) leaves no location to place the [`Item`] on
+    UnsatisfiableConstraint { world_index: usize, item: Item },
+    /// A [`PlacementConstraint`](crate::settings::PlacementConstraint) was placed on Spirit Light, which has randomized amounts
+    SpiritLightConstraint { world_index: usize },
+    /// One of the [`HintSettings::triggers`](crate::settings::HintSettings::triggers) is not valid trigger syntax
+    InvalidHintTrigger {
+        world_index: usize,
+        trigger: String,
+        message: String,
+    },
+    /// Way of the Hero or barren hints were requested without [`UniverseSettings::required_items`](crate::settings::UniverseSettings::required_items)
+    HintsNeedRequiredItems { world_index: usize },
+    /// The [`ItemPoolSettings`](crate::settings::ItemPoolSettings) contain Spirit Light as an item, instead of using the `spirit_light` total
+    SpiritLightInItemPool { world_index: usize },
+    /// The [`ItemPoolSettings`](crate::settings::ItemPoolSettings) contain more items than there are locations to place them on
+    ItemPoolTooLarge {
+        world_index: usize,
+        items: usize,
+        locations: usize,
+    },
+    /// The configured [`Goal`](crate::settings::Goal)s contradict each other or the [`Goal`](crate::settings::Goal)s of a header
+    ContradictingGoals { world_index: usize, message: String },
+}
+impl Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SettingsError::AmbiguousHeaderName { world_index, name } => write!(
+                f,
+                "(World {world_index}): Ambiguous name: {name} used both as a file header and an inline header"
+            ),
+            SettingsError::ConflictingHeaderConfig {
+                world_index,
+                header_name,
+                config_name,
+                values: (prior, value),
+            } => write!(
+                f,
+                "(World {world_index}): provided multiple values for configuration parameter {config_name} for header {header_name} ({prior} and {value})"
+            ),
+            SettingsError::SpawnNotFound { world_index, spawn } => {
+                write!(f, "(World {world_index}): Spawn {spawn} not found")
+            }
+            SettingsError::InvalidSpawn { world_index, spawn } => {
+                write!(f, "(World {world_index}): {spawn} is not a valid spawn")
+            }
+            SettingsError::NoSpawnLocations { world_index } => write!(
+                f,
+                "(World {world_index}): No valid spawn locations available"
+            ),
+            SettingsError::ExperimentalDoors => write!(
+                f,
+                "Door randomization is experimental since the randomizer client can't apply door layouts yet, enable experimentalDoors in the tuning to use it anyway"
+            ),
+            SettingsError::InvalidTuning { message } => {
+                write!(f, "Invalid generation tuning: {message}")
+            }
+            SettingsError::UnknownConstraintLocation {
+                world_index,
+                identifier,
+            } => write!(
+                f,
+                "(World {world_index}): Placement constraint refers to unknown location {identifier}"
+            ),
+            SettingsError::UnsatisfiableConstraint { world_index, item } => write!(
+                f,
+                "(World {world_index}): Placement constraints leave no location for {item}"
+            ),
+            SettingsError::SpiritLightConstraint { world_index } => write!(
+                f,
+                "(World {world_index}): Spirit Light cannot be constrained since its amounts are randomized"
+            ),
+            SettingsError::InvalidHintTrigger {
+                world_index,
+                trigger,
+                message,
+            } => write!(
+                f,
+                "(World {world_index}): Invalid hint trigger {trigger}: {message}"
+            ),
+            SettingsError::HintsNeedRequiredItems { world_index } => write!(
+                f,
+                "(World {world_index}): Way of the Hero and barren hints need required items to be enabled"
+            ),
+            SettingsError::SpiritLightInItemPool { world_index } => write!(
+                f,
+                "(World {world_index}): Spirit Light can't be added to the item pool as an item, set the spirit light total instead"
+            ),
+            SettingsError::ItemPoolTooLarge {
+                world_index,
+                items,
+                locations,
+            } => write!(
+                f,
+                "(World {world_index}): The item pool contains {items} items, but there are only {locations} locations"
+            ),
+            SettingsError::ContradictingGoals {
+                world_index,
+                message,
+            } => write!(f, "(World {world_index}): {message}"),
+        }
+    }
+}
+impl Error for SettingsError {}
+
+/// A header that failed to be read, parsed or applied
+#[derive(Debug, Clone)]
+pub enum HeaderError {
+    /// The header could not be read through the provided [`FileAccess`](crate::files::FileAccess)
+    Read {
+        world_index: usize,
+        header_name: String,
+        message: String,
+    },
+    /// The header syntax was invalid
+    Parse {
+        world_index: usize,
+        header_name: String,
+        errors: ParseErrorCollection,
+    },
+    /// The header parsed, but building it failed, e.g. due to invalid parameters
+    Build {
+        world_index: usize,
+        header_name: String,
+        message: String,
+    },
+    /// Two active headers exclude each other
+    Incompatible {
+        world_index: usize,
+        header_name: String,
+        other: String,
+    },
+    /// More than one header tried to customize the same [`Item`]
+    DuplicateItemDetails {
+        world_index: usize,
+        header_name: String,
+        item: Item,
+    },
+}
+impl Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HeaderError::Read {
+                world_index,
+                header_name,
+                message,
+            } => write!(
+                f,
+                "(World {world_index}): Failed to read header {header_name}: {message}"
+            ),
+            HeaderError::Parse {
+                world_index,
+                header_name,
+                errors,
+            } => write!(
+                f,
+                "(World {world_index}): Error in header {header_name}:\n{}",
+                errors.verbose_display()
+            ),
+            HeaderError::Build {
+                world_index,
+                header_name,
+                message,
+            } => write!(
+                f,
+                "(World {world_index}): Error in header {header_name}: {message}"
+            ),
+            HeaderError::Incompatible {
+                world_index,
+                header_name,
+                other,
+            } => write!(
+                f,
+                "(World {world_index}): headers {other} and {header_name} are incompatible"
+            ),
+            HeaderError::DuplicateItemDetails {
+                world_index,
+                header_name,
+                item,
+            } => write!(
+                f,
+                "(World {world_index}): multiple headers tried to customize the item {item} (last attempt by {header_name})"
+            ),
+        }
+    }
+}
+impl Error for HeaderError {}
+
+/// The logic [`Graph`](crate::world::Graph) doesn't support what the settings request
+#[derive(Debug, Clone)]
+pub enum LogicError {
+    /// The default spawn could not be used for the total reach check
+    DefaultSpawn { world_index: usize, message: String },
+    /// Spawning on a location that has no coordinates
+    SpawnWithoutPosition { world_index: usize, spawn: String },
+    /// A custom [`Goal`](crate::settings::Goal) can't be achieved even with every item
+    UnreachableGoal { world_index: usize, goal: String },
+    /// Door randomization found no door layout that keeps every location reachable
+    DoorLayout { message: String },
+}
+impl Display for LogicError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogicError::DefaultSpawn {
+                world_index,
+                message,
+            } => write!(f, "(World {world_index}): {message}"),
+            LogicError::SpawnWithoutPosition { world_index, spawn } => write!(
+                f,
+                "(World {world_index}): Cannot spawn on {spawn} which has no specified coordinates"
+            ),
+            LogicError::UnreachableGoal { world_index, goal } => write!(
+                f,
+                "(World {world_index}): The goal {goal} can't be achieved with the available items"
+            ),
+            LogicError::DoorLayout { message } => write!(f, "{message}"),
+        }
+    }
+}
+impl Error for LogicError {}
+
+/// A single attempt at placing items failed
+///
+/// Since placements are random, another attempt may succeed
+#[derive(Debug, Clone)]
+pub enum PlacementError {
+    /// A shop location has no entry in the shop prices table
+    MissingShopPrice { world_index: usize, node: String },
+    /// Randomizing a shop price overflowed
+    ShopPriceOverflow { world_index: usize, item: Item },
+    /// There were no slots left to place forced progression
+    NotEnoughSlots { world_index: usize, item: Item },
+    /// Choosing one of the progression options failed
+    ChooseProgression {
+        world_index: usize,
+        source: WeightedError,
+    },
+    /// Nothing was reachable from the spawn locations
+    NothingReachable {
+        /// Spawn identifier per world
+        spawns: Vec<String>,
+    },
+    /// No progression could be found to reach the remaining locations
+    UnreachedLocations {
+        /// Identifiers of the unreached locations per world
+        unreached: Vec<Vec<String>>,
+    },
+    /// None of the remaining slots satisfy the [`PlacementConstraint`](crate::settings::PlacementConstraint)s of the [`Item`]
+    ConstraintUnsatisfiable { world_index: usize, item: Item },
+    /// An [`Item`] was about to be placed somewhere its [`PlacementConstraint`](crate::settings::PlacementConstraint)s forbid
+    ConstraintViolated {
+        world_index: usize,
+        item: Item,
+        node: String,
+    },
+}
+impl Display for PlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlacementError::MissingShopPrice { world_index, node } => write!(
+                f,
+                "(World {world_index}): {node} claims to be a shop location, but doesn't have an entry in the shop prices table!"
+            ),
+            PlacementError::ShopPriceOverflow { world_index, item } => write!(
+                f,
+                "(World {world_index}): Overflowed shop price for {item} after adding a random amount to it"
+            ),
+            PlacementError::NotEnoughSlots { world_index, item } => write!(
+                f,
+                "(World {world_index}): Not enough slots to place forced progression {item}"
+            ),
+            PlacementError::ChooseProgression {
+                world_index,
+                source,
+            } => write!(
+                f,
+                "(World {world_index}): Error choosing progression: {source}"
+            ),
+            PlacementError::NothingReachable { .. } => {
+                write!(f, "Failed to reach anything from spawn location")
+            }
+            PlacementError::UnreachedLocations { .. } => {
+                write!(f, "Failed to reach all locations")
+            }
+            PlacementError::ConstraintUnsatisfiable { world_index, item } => write!(
+                f,
+                "(World {world_index}): No remaining slot satisfies the placement constraints for {item}"
+            ),
+            PlacementError::ConstraintViolated {
+                world_index,
+                item,
+                node,
+            } => write!(
+                f,
+                "(World {world_index}): Placement constraints forbid placing {item} on {node}"
+            ),
+        }
+    }
+}
+impl Error for PlacementError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PlacementError::ChooseProgression { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use std::fmt::{self, Display};
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    item::{Item, Message},
+    log,
+    settings::HintSettings,
+    uber_state::UberStateTrigger,
+    util::Zone,
+};
+
+use super::{
+    error::SettingsError,
+    spoiler::{SeedSpoiler, SpoilerPlacement},
+    Placement, SeedWorld,
+};
+
+/// A single piece of information derived from the final placements
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Hint {
+    /// The [`Zone`] contains at least one required item
+    WayOfTheHero(Zone),
+    /// The [`Zone`] contains no required items
+    Barren(Zone),
+    /// The amount of skills and teleporters in the [`Zone`]
+    ZoneCount {
+        zone: Zone,
+        skills: usize,
+        teleporters: usize,
+    },
+}
+impl Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Hint::WayOfTheHero(zone) => write!(f, "{zone} is on the Way of the Hero"),
+            Hint::Barren(zone) => write!(f, "Nothing required in {zone}"),
+            Hint::ZoneCount {
+                zone,
+                skills,
+                teleporters,
+            } => write!(f, "{zone}: {skills} Skills, {teleporters} Teleporters"),
+        }
+    }
+}
+
+/// Checks that the hints of a world can be generated with the given [`UniverseSettings::required_items`](crate::settings::UniverseSettings::required_items)
+pub(super) fn validate_hints(
+    world_index: usize,
+    hints: &HintSettings,
+    required_items: bool,
+) -> Result<(), SettingsError> {
+    if !required_items && (hints.way_of_the_hero > 0 || hints.barren > 0) {
+        return Err(SettingsError::HintsNeedRequiredItems { world_index });
+    }
+
+    Ok(())
+}
+
+/// Parses the hint triggers of a world, defaulting to spawn
+pub(super) fn parse_hint_triggers(
+    world_index: usize,
+    hints: &HintSettings,
+) -> Result<Vec<UberStateTrigger>, SettingsError> {
+    if hints.triggers.is_empty() {
+        return Ok(vec![UberStateTrigger::spawn()]);
+    }
+
+    hints
+        .triggers
+        .iter()
+        .map(|trigger| {
+            trigger.parse::<UberStateTrigger>().map_err(|message| {
+                SettingsError::InvalidHintTrigger {
+                    world_index,
+                    trigger: trigger.clone(),
+                    message,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Placements required for any goal of any world
+fn required_placements(spoiler: &SeedSpoiler) -> Vec<&SpoilerPlacement> {
+    spoiler
+        .required
+        .iter()
+        .flatten()
+        .flat_map(|required| &required.placements)
+        .collect()
+}
+
+/// Generates the hints requested by the [`HintSettings`] of each world and places them as [`Message`]s on the respective triggers
+pub(super) fn generate_hints(
+    worlds: &mut [SeedWorld],
+    triggers: Vec<Vec<UberStateTrigger>>,
+    spoiler: &SeedSpoiler,
+    rng: &mut impl Rng,
+) {
+    let required = required_placements(spoiler);
+
+    for ((world_index, world), triggers) in worlds.iter_mut().enumerate().zip(triggers) {
+        let hints = world_hints(
+            world_index,
+            &world.world_settings.hints,
+            spoiler,
+            &required,
+            rng,
+        );
+
+        log::trace!("(World {}): Generated {} hints", world_index, hints.len());
+
+        if hints.is_empty() {
+            continue;
+        }
+
+        let per_trigger = hints.len().div_ceil(triggers.len());
+        for (trigger, hints) in triggers.into_iter().zip(hints.chunks(per_trigger)) {
+            let text = hints.iter().map(Hint::to_string).collect::<Vec<_>>();
+            world.placements.push(Placement {
+                node: None,
+                trigger,
+                item: Item::Message(Message::new(text.join("\\n"))),
+            });
+        }
+    }
+}
+
+/// Generates the hints for a single world
+///
+/// Every [`Zone`] containing placements of the world is considered, including the shops
+fn world_hints(
+    world_index: usize,
+    settings: &HintSettings,
+    spoiler: &SeedSpoiler,
+    required: &[&SpoilerPlacement],
+    rng: &mut impl Rng,
+) -> Vec<Hint> {
+    let mut hints = Vec::new();
+    if !settings.any() {
+        return hints;
+    }
+
+    let world_placements = spoiler
+        .groups
+        .iter()
+        .flat_map(|group| &group.placements)
+        .filter(|placement| placement.origin_world_index == world_index)
+        .collect::<Vec<_>>();
+    let zones = world_placements
+        .iter()
+        .filter_map(|placement| placement.location.zone)
+        .unique()
+        .sorted()
+        .collect::<Vec<_>>();
+    let required_zones = required
+        .iter()
+        .filter(|placement| placement.origin_world_index == world_index)
+        .filter_map(|placement| placement.location.zone)
+        .collect::<Vec<_>>();
+
+    let (mut way_of_the_hero, mut barren): (Vec<_>, Vec<_>) = zones
+        .iter()
+        .copied()
+        .partition(|zone| required_zones.contains(zone));
+
+    way_of_the_hero.shuffle(rng);
+    way_of_the_hero.truncate(settings.way_of_the_hero);
+    hints.extend(way_of_the_hero.into_iter().map(Hint::WayOfTheHero));
+
+    barren.shuffle(rng);
+    barren.truncate(settings.barren);
+    hints.extend(barren.into_iter().map(Hint::Barren));
+
+    if settings.zone_counts {
+        for zone in zones {
+            let own_placements = world_placements.iter().filter(|placement| {
+                placement.target_world_index == world_index && placement.location.zone == Some(zone)
+            });
+            let skills = own_placements
+                .clone()
+                .filter(|placement| matches!(placement.item, Item::Skill(_)))
+                .count();
+            let teleporters = own_placements
+                .filter(|placement| matches!(placement.item, Item::Teleporter(_)))
+                .count();
+            hints.push(Hint::ZoneCount {
+                zone,
+                skills,
+                teleporters,
+            });
+        }
+    }
+
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generator::spoiler::{NodeSummary, RequiredPlacements, SpoilerGroup},
+        inventory::Inventory,
+        item::{Skill, Teleporter},
+    };
+    use rand::rngs::StdRng;
+    use rand_seeder::Seeder;
+
+    fn placement(item: Item, identifier: &str, zone: Option<Zone>) -> SpoilerPlacement {
+        SpoilerPlacement {
+            origin_world_index: 0,
+            target_world_index: 0,
+            location: NodeSummary {
+                identifier: identifier.to_string(),
+                position: None,
+                zone,
+            },
+            item_name: item.to_string(),
+            item,
+        }
+    }
+
+    #[test]
+    fn hints_cover_every_zone() {
+        let launch = placement(
+            Item::Skill(Skill::Launch),
+            "RuinsTN.LaunchTree",
+            Some(Zone::Ruins),
+        );
+        let spoiler = SeedSpoiler {
+            spawns: vec!["MarshSpawn.Main".to_string()],
+            groups: vec![SpoilerGroup {
+                reachable: vec![vec![]],
+                forced_items: Inventory::default(),
+                placements: vec![
+                    launch.clone(),
+                    placement(
+                        Item::Skill(Skill::Grapple),
+                        "OpherShop.WaterBreath",
+                        Some(Zone::Shop),
+                    ),
+                    placement(
+                        Item::Teleporter(Teleporter::Marsh),
+                        "MarshSpawn.RockHC",
+                        Some(Zone::Marsh),
+                    ),
+                    placement(Item::SpiritLight(50), "Spawn", None),
+                ],
+            }],
+            required: vec![vec![RequiredPlacements {
+                goal: None,
+                met: true,
+                placements: vec![launch],
+            }]],
+            doors: Vec::new(),
+        };
+        let settings = HintSettings {
+            way_of_the_hero: 5,
+            barren: 5,
+            zone_counts: true,
+            triggers: Vec::new(),
+        };
+        let mut rng: StdRng = Seeder::from("hints").make_rng();
+
+        let mut hints = world_hints(
+            0,
+            &settings,
+            &spoiler,
+            &required_placements(&spoiler),
+            &mut rng,
+        );
+        let zone_counts = hints.split_off(3);
+        assert_eq!(hints[0], Hint::WayOfTheHero(Zone::Ruins));
+        assert!(hints.contains(&Hint::Barren(Zone::Marsh)));
+        assert!(hints.contains(&Hint::Barren(Zone::Shop)));
+        assert_eq!(
+            zone_counts,
+            vec![
+                Hint::ZoneCount {
+                    zone: Zone::Marsh,
+                    skills: 0,
+                    teleporters: 1,
+                },
+                Hint::ZoneCount {
+                    zone: Zone::Ruins,
+                    skills: 1,
+                    teleporters: 0,
+                },
+                Hint::ZoneCount {
+                    zone: Zone::Shop,
+                    skills: 1,
+                    teleporters: 0,
+                },
+            ]
+        );
+
+        let hints = world_hints(
+            1,
+            &settings,
+            &spoiler,
+            &required_placements(&spoiler),
+            &mut rng,
+        );
+        assert_eq!(hints, vec![]);
+    }
+}
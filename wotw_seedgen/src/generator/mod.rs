@@ -1,60 +1,133 @@
+mod error;
+mod hints;
+mod observer;
 mod placement;
+mod playthrough;
 mod seed;
 mod spoiler;
 
+pub use error::*;
+pub use observer::*;
 pub use placement::*;
 pub use seed::*;
 pub use spoiler::*;
 
 use std::{cmp::Ordering, fmt::Write};
 
+use itertools::Itertools;
 use rand::{prelude::StdRng, Rng};
 use rand_seeder::Seeder;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::files::FileAccess;
 use crate::header::{self, Header, HeaderBuild};
-use crate::item::{Item, Message, UberStateOperator};
+use crate::item::{Command, Item, Message, UberStateItem, UberStateOperator, UberStateValue};
 use crate::log;
-use crate::settings::{Goal, HeaderConfig, InlineHeader, UniverseSettings};
-use crate::uber_state::UberStateTrigger;
-use crate::world::{Graph, Pool, World};
-
-use placement::generate_placements;
+use crate::settings::{
+    ConstraintMode, CustomGoal, Goal, GoalModes, HeaderConfig, InlineHeader, JunkFill,
+    LocationFilter, UniverseSettings, WorldSettings,
+};
+use crate::uber_state::{
+    UberIdentifier, UberStateComparator, UberStateCondition, UberStateTrigger, UberType,
+};
+use crate::util::constants::{CUSTOM_GOAL_COMMENT, CUSTOM_GOAL_GROUP, JUNK_SPIRIT_LIGHT};
+use crate::world::{doors::randomize_doors, Graph, Pool, World};
+
+use hints::{generate_hints, parse_hint_triggers, validate_hints};
+use placement::{generate_placements, generate_placements_parallel};
+pub(crate) use playthrough::location_goal_met;
+use playthrough::required_placements;
 
 pub fn generate_seed<'graph, 'settings>(
     graph: &'graph Graph,
     file_access: &impl FileAccess,
     settings: &'settings UniverseSettings,
-) -> Result<Seed<'graph, 'settings>, String> {
+) -> Result<Seed<'graph, 'settings>, GenerationError> {
+    generate_seed_observed(graph, file_access, settings, &NO_OBSERVER)
+}
+
+/// Like [`generate_seed`], but reports progress to the [`GenerationObserver`] and allows it to cancel the generation
+pub fn generate_seed_observed<'graph, 'settings>(
+    graph: &'graph Graph,
+    file_access: &impl FileAccess,
+    settings: &'settings UniverseSettings,
+    observer: &impl GenerationObserver,
+) -> Result<Seed<'graph, 'settings>, GenerationError> {
+    settings
+        .tuning
+        .validate()
+        .map_err(|message| SettingsError::InvalidTuning { message })?;
+    if settings.door_randomization && !settings.tuning.experimental_doors {
+        return Err(SettingsError::ExperimentalDoors.into());
+    }
+    let hint_triggers = settings
+        .world_settings
+        .iter()
+        .enumerate()
+        .map(|(world_index, world_settings)| {
+            validate_placement_constraints(world_index, graph, world_settings)?;
+            validate_item_pool(world_index, graph, world_settings)?;
+            validate_hints(world_index, &world_settings.hints, settings.required_items)?;
+            parse_hint_triggers(world_index, &world_settings.hints)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
     let mut rng: StdRng = Seeder::from(&settings.seed).make_rng();
     log::trace!("Seeded RNG with {}", settings.seed);
 
+    let (doors, door_connections) = if settings.door_randomization {
+        log::warning!("The randomizer client can't read door layouts yet, the shuffled doors will only be part of the spoiler");
+        let (doors, door_connections) = randomize_doors(graph, settings, &mut rng)
+            .map_err(|message| LogicError::DoorLayout { message })?;
+        (Some(doors), door_connections)
+    } else {
+        (None, Vec::new())
+    };
+
     let (worlds, (flags, headers)): (Vec<_>, (Vec<_>, Vec<_>)) = settings
         .world_settings
         .iter()
-        .map(|world_settings| {
+        .enumerate()
+        .map(|(world_index, world_settings)| {
             let mut world = World::new_spawn(graph, world_settings);
-            world.pool = Pool::preset();
+            world.player.doors = doors.clone();
+            world.pool = Pool::from_settings(&world_settings.item_pool);
 
-            let (goals, flags, headers) = parse_headers(&mut world, file_access, &mut rng)?;
+            let (goals, flags, headers) =
+                parse_headers(world_index, &mut world, file_access, &mut rng)?;
             world.goals = goals;
+            fill_junk(world_index, &mut world);
 
             Ok((world, (flags, headers)))
         })
-        .collect::<Result<Vec<_>, String>>()?
+        .collect::<Result<Vec<_>, GenerationError>>()?
         .into_iter()
         .unzip();
 
-    let (mut worlds, spoiler) = generate_placements(graph, &worlds, &mut rng)?;
+    let (mut seed_worlds, mut spoiler) = if settings.tuning.parallel_attempts {
+        generate_placements_parallel(graph, &worlds, &settings.seed, &settings.tuning, observer)?
+    } else {
+        generate_placements(graph, &worlds, &settings.tuning, observer, &mut rng)?
+    };
 
-    for ((world, flags), headers) in worlds.iter_mut().zip(flags).zip(headers) {
+    spoiler.doors = door_connections;
+
+    if settings.required_items {
+        let spawns = seed_worlds
+            .iter()
+            .map(|seed_world| seed_world.spawn)
+            .collect::<Vec<_>>();
+        spoiler.required = required_placements(&worlds, &spawns, &spoiler);
+    }
+    generate_hints(&mut seed_worlds, hint_triggers, &spoiler, &mut rng);
+
+    for ((world, flags), headers) in seed_worlds.iter_mut().zip(flags).zip(headers) {
         world.flags = flags;
         world.headers = headers;
     }
 
     Ok(Seed {
-        worlds,
+        worlds: seed_worlds,
         graph,
         settings,
         spoiler,
@@ -62,24 +135,27 @@ pub fn generate_seed<'graph, 'settings>(
 }
 
 fn parse_headers(
+    world_index: usize,
     world: &mut World,
     file_access: &impl FileAccess,
     rng: &mut impl Rng,
-) -> Result<(Vec<Goal>, Vec<String>, String), String> {
+) -> Result<(Vec<Goal>, Vec<String>, String), GenerationError> {
     validate_header_names(
+        world_index,
         &world.player.settings.headers,
         &world.player.settings.inline_headers,
     )?;
 
-    let mut config_map = build_config_map(&world.player.settings.header_config)?;
+    let mut config_map = build_config_map(world_index, &world.player.settings.header_config)?;
 
     let mut headers = vec![];
     let mut includes = FxHashSet::default();
     includes.extend(world.player.settings.headers.iter().cloned());
 
     for header_name in &world.player.settings.headers {
-        let header = file_access.read_header(header_name)?;
+        let header = read_header(world_index, header_name, file_access)?;
         parse_header(
+            world_index,
             header_name.clone(),
             header,
             &mut headers,
@@ -98,6 +174,7 @@ fn parse_headers(
             .unwrap_or_else(|| "Anonymous Header".to_string());
         let header = inline_header.content.clone();
         parse_header(
+            world_index,
             header_name,
             header,
             &mut headers,
@@ -111,7 +188,7 @@ fn parse_headers(
     let mut excludes = FxHashMap::default();
     let mut seed_contents = String::new();
     let mut flags = vec![];
-    let mut goals = vec![];
+    let mut goals = GoalModes::default();
     let mut state_sets = vec![];
 
     flags.push(world.player.settings.difficulty.to_string());
@@ -138,7 +215,13 @@ fn parse_headers(
             }
 
             flags.append(&mut header.flags);
-            goals.append(&mut header.goals);
+            for goal in header.goals {
+                goals.add(goal).map_err(|message| HeaderError::Build {
+                    world_index,
+                    header_name: header_name.clone(),
+                    message,
+                })?;
+            }
 
             for preplacement in header.preplacements {
                 block_spawn_sets(&preplacement, world);
@@ -160,11 +243,12 @@ fn parse_headers(
             }
 
             for (item, details) in header.item_details {
-                let display = item.to_string();
-                if world.custom_items.insert(item, details).is_some() {
-                    return Err(format!(
-                        "multiple headers tried to customize the item {display}"
-                    ));
+                if world.custom_items.insert(item.clone(), details).is_some() {
+                    return Err(HeaderError::DuplicateItemDetails {
+                        world_index,
+                        header_name,
+                        item,
+                    });
                 }
             }
 
@@ -176,9 +260,12 @@ fn parse_headers(
 
     for header_name in &header_names {
         if let Some(other) = excludes.get(header_name) {
-            return Err(format!(
-                "headers {other} and {header_name} are incompatible"
-            ));
+            return Err(HeaderError::Incompatible {
+                world_index,
+                header_name: header_name.clone(),
+                other: other.clone(),
+            }
+            .into());
         }
     }
     for header_with_parameters in config_map.keys() {
@@ -192,7 +279,14 @@ fn parse_headers(
         }
     }
 
-    goals.extend(world.player.settings.goals.iter().cloned());
+    for goal in world.player.settings.goals.iter() {
+        goals
+            .add(goal.clone())
+            .map_err(|message| SettingsError::ContradictingGoals {
+                world_index,
+                message,
+            })?;
+    }
 
     for flag in goals.iter().map(Goal::flag_name) {
         flags.push(flag.to_string());
@@ -201,14 +295,36 @@ fn parse_headers(
     let mut header_block = String::new();
 
     write!(header_block, "{seed_contents}").unwrap();
+    let custom_goals = goals.iter().filter_map(|goal| match goal {
+        Goal::Custom(goal) => Some(goal),
+        _ => None,
+    });
+    for (goal_index, goal) in custom_goals.enumerate() {
+        write_custom_goal(&mut header_block, goal_index, goal);
+    }
     if !state_sets.is_empty() {
         writeln!(header_block, "// Sets: {}", state_sets.join(", ")).unwrap();
     }
 
-    Ok((goals, flags, header_block))
+    Ok((goals.into_iter().collect(), flags, header_block))
+}
+
+fn read_header(
+    world_index: usize,
+    header_name: &str,
+    file_access: &impl FileAccess,
+) -> Result<String, HeaderError> {
+    file_access
+        .read_header(header_name)
+        .map_err(|message| HeaderError::Read {
+            world_index,
+            header_name: header_name.to_string(),
+            message,
+        })
 }
 
 fn parse_header(
+    world_index: usize,
     header_name: String,
     header: String,
     headers: &mut Vec<(String, HeaderBuild)>,
@@ -216,25 +332,29 @@ fn parse_header(
     config_map: &mut FxHashMap<String, FxHashMap<String, String>>,
     file_access: &impl FileAccess,
     rng: &mut impl Rng,
-) -> Result<(), String> {
+) -> Result<(), HeaderError> {
     log::trace!("Parsing header {header_name}");
 
     let header_config = config_map.remove(&header_name).unwrap_or_default();
 
     let header = Header::parse(header, rng)
-        .map_err(|err| {
-            format!(
-                "Error in header {}:\n{}",
-                header_name,
-                err.verbose_display()
-            )
+        .map_err(|errors| HeaderError::Parse {
+            world_index,
+            header_name: header_name.clone(),
+            errors,
         })?
-        .build(header_config)?;
+        .build(header_config)
+        .map_err(|message| HeaderError::Build {
+            world_index,
+            header_name: header_name.clone(),
+            message,
+        })?;
 
     for include in &header.includes {
         if includes.insert(include.clone()) {
-            let header = file_access.read_header(include)?;
+            let header = read_header(world_index, include, file_access)?;
             parse_header(
+                world_index,
                 include.clone(),
                 header,
                 headers,
@@ -253,15 +373,17 @@ fn parse_header(
 
 /// verifies that inline headers don't claim names already in use
 fn validate_header_names(
+    world_index: usize,
     headers: &FxHashSet<String>,
     inline_headers: &[InlineHeader],
-) -> Result<(), String> {
+) -> Result<(), SettingsError> {
     for inline_header in inline_headers {
         if let Some(name) = &inline_header.name {
             if headers.contains(name) {
-                return Err(format!(
-                    "Ambiguous name: {name} used both as a file header and an inline header"
-                ));
+                return Err(SettingsError::AmbiguousHeaderName {
+                    world_index,
+                    name: name.clone(),
+                });
             }
         }
     }
@@ -269,9 +391,158 @@ fn validate_header_names(
     Ok(())
 }
 
+/// Writes lines that set the goal's uberState in [`CUSTOM_GOAL_GROUP`] once all of its conditions are met
+///
+/// The check runs whenever one of the conditions' uberStates changes and on load
+fn write_custom_goal(header_block: &mut String, goal_index: usize, goal: &CustomGoal) {
+    #[allow(clippy::cast_possible_truncation)]
+    let identifier = UberIdentifier::new(CUSTOM_GOAL_GROUP, goal_index as u16);
+    let setter =
+        UberStateItem::simple_setter(identifier, UberType::Bool, UberStateValue::Bool(true));
+    let item = goal.conditions.iter().rev().fold(setter, |item, trigger| {
+        let uber_identifier = trigger.identifier;
+        let item = Box::new(item);
+        // uberState values are integers, so inclusive comparisons can be shifted by one
+        #[allow(clippy::cast_precision_loss)]
+        let (comparator, value) = match &trigger.condition {
+            None => (UberStateComparator::Greater, 0.),
+            Some(UberStateCondition { comparator, value }) => {
+                let value = *value as f32;
+                match comparator {
+                    UberStateComparator::GreaterOrEquals => {
+                        (UberStateComparator::Greater, value - 1.)
+                    }
+                    UberStateComparator::LessOrEquals => (UberStateComparator::Less, value + 1.),
+                    comparator => (comparator.clone(), value),
+                }
+            }
+        };
+        let value = value.into();
+        Item::Command(match comparator {
+            UberStateComparator::Equals => Command::IfEqual {
+                uber_identifier,
+                value,
+                item,
+            },
+            UberStateComparator::Greater => Command::IfGreater {
+                uber_identifier,
+                value,
+                item,
+            },
+            _ => Command::IfLess {
+                uber_identifier,
+                value,
+                item,
+            },
+        })
+    });
+
+    writeln!(header_block, "{CUSTOM_GOAL_COMMENT}{}", goal.flag).unwrap();
+    let triggers = goal
+        .conditions
+        .iter()
+        .map(|condition| condition.identifier)
+        .chain([UberIdentifier::load()])
+        .unique();
+    for trigger in triggers {
+        writeln!(header_block, "{}|{}", trigger.code(), item.code()).unwrap();
+    }
+}
+
+fn validate_placement_constraints(
+    world_index: usize,
+    graph: &Graph,
+    world_settings: &WorldSettings,
+) -> Result<(), SettingsError> {
+    for constraint in &world_settings.placement_constraints {
+        if matches!(constraint.item, Item::SpiritLight(_)) {
+            return Err(SettingsError::SpiritLightConstraint { world_index });
+        }
+
+        for location in &constraint.locations {
+            if let LocationFilter::Node(identifier) = location {
+                if !graph
+                    .nodes
+                    .iter()
+                    .any(|node| node.identifier() == identifier)
+                {
+                    return Err(SettingsError::UnknownConstraintLocation {
+                        world_index,
+                        identifier: identifier.clone(),
+                    });
+                }
+            }
+        }
+
+        if constraint.mode == ConstraintMode::Only
+            && !graph.nodes.iter().any(|node| {
+                node.can_place() && world_settings.allows_placement(&constraint.item, node)
+            })
+        {
+            return Err(SettingsError::UnsatisfiableConstraint {
+                world_index,
+                item: constraint.item.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_item_pool(
+    world_index: usize,
+    graph: &Graph,
+    world_settings: &WorldSettings,
+) -> Result<(), SettingsError> {
+    let item_pool = &world_settings.item_pool;
+    let junk_spirit_light = matches!(item_pool.junk, JunkFill::Item(Item::SpiritLight(_)));
+    if junk_spirit_light
+        || item_pool
+            .items
+            .iter()
+            .any(|item_count| matches!(item_count.item, Item::SpiritLight(_)))
+    {
+        return Err(SettingsError::SpiritLightInItemPool { world_index });
+    }
+
+    let items = Pool::from_settings(item_pool).inventory.item_count() as usize;
+    let locations = graph.nodes.iter().filter(|node| node.can_place()).count();
+    if items > locations {
+        return Err(SettingsError::ItemPoolTooLarge {
+            world_index,
+            items,
+            locations,
+        });
+    }
+
+    Ok(())
+}
+
+/// Adds copies of the [`JunkFill::Item`] to the pool until it fills the free locations
+fn fill_junk(world_index: usize, world: &mut World) {
+    if let JunkFill::Item(item) = &world.player.settings.item_pool.junk {
+        let free_locations = world
+            .graph
+            .nodes
+            .iter()
+            .filter(|node| {
+                node.can_place() && !world.preplacements.contains_key(node.trigger().unwrap())
+            })
+            .count()
+            .saturating_sub(1); // 1 will be 1xp
+        let spirit_light_locations = (world.pool.spirit_light / JUNK_SPIRIT_LIGHT) as usize;
+        let junk = free_locations
+            .saturating_sub(world.pool.inventory.item_count() as usize + spirit_light_locations);
+
+        log::trace!("(World {}): Adding {} {} as junk", world_index, junk, item);
+        world.pool.grant(item.clone(), junk as u32);
+    }
+}
+
 fn build_config_map(
+    world_index: usize,
     header_config: &[HeaderConfig],
-) -> Result<FxHashMap<String, FxHashMap<String, String>>, String> {
+) -> Result<FxHashMap<String, FxHashMap<String, String>>, SettingsError> {
     let mut config_map = FxHashMap::<String, FxHashMap<_, _>>::default();
 
     for config in header_config {
@@ -281,7 +552,12 @@ fn build_config_map(
             .insert(config.config_name.clone(), config.config_value.clone())
         {
             if prior != config.config_value {
-                return Err(format!("provided multiple values for configuration parameter {} for header {} ({} and {})", config.config_name, config.header_name, prior, config.config_value));
+                return Err(SettingsError::ConflictingHeaderConfig {
+                    world_index,
+                    header_name: config.header_name.clone(),
+                    config_name: config.config_name.clone(),
+                    values: (prior, config.config_value.clone()),
+                });
             }
         }
     }
@@ -317,3 +593,388 @@ fn block_spawn_sets(preplacement: &header::Pickup, world: &mut World) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{
+        files::{self, FILE_SYSTEM_ACCESS, NO_FILE_ACCESS},
+        item::{Resource, Shard, Skill},
+        logic,
+        settings::{
+            CustomGoal, HintSettings, ItemCount, ItemPoolSettings, PlacementConstraint, Spawn,
+        },
+        util::Zone,
+        world::graph::Node,
+    };
+
+    #[test]
+    fn structured_errors() {
+        let mut universe_settings = UniverseSettings::default();
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+
+        universe_settings.world_settings[0].spawn = Spawn::Set("Nowhere".to_string());
+        let err = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).err();
+        assert!(matches!(
+            err,
+            Some(GenerationError::Settings(SettingsError::SpawnNotFound { world_index: 0, ref spawn })) if spawn == "Nowhere"
+        ));
+
+        universe_settings.world_settings[0].spawn = Spawn::default();
+        universe_settings.world_settings[0]
+            .headers
+            .insert("missing".to_string());
+        let err = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).err();
+        assert!(matches!(
+            err,
+            Some(GenerationError::Header(HeaderError::Read { ref header_name, .. })) if header_name == "missing"
+        ));
+    }
+
+    #[test]
+    fn spawn_lists() {
+        let mut universe_settings = UniverseSettings::default();
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+
+        universe_settings.world_settings[0].spawn = Spawn::Weighted(vec![
+            ("MarshSpawn.Main".to_string(), 0),
+            ("HowlsDen.Teleporter".to_string(), 1),
+        ]);
+        // Same as the rspawn preset, without it there's no early progression away from the default spawn
+        universe_settings.world_settings[0].headers =
+            FxHashSet::from_iter(["better_random_spawn".to_string()]);
+        let seed = generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings).unwrap();
+        assert_eq!(seed.worlds[0].spawn.identifier(), "HowlsDen.Teleporter");
+
+        universe_settings.world_settings[0].headers.clear();
+        universe_settings.world_settings[0].spawn =
+            Spawn::OneOf(vec!["MarshSpawn.Main".to_string(), "Nowhere".to_string()]);
+        let err = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).err();
+        assert!(matches!(
+            err,
+            Some(GenerationError::Settings(SettingsError::SpawnNotFound { world_index: 0, ref spawn })) if spawn == "Nowhere"
+        ));
+    }
+
+    struct CancelAfterGroups {
+        groups: AtomicUsize,
+        limit: usize,
+    }
+    impl GenerationObserver for CancelAfterGroups {
+        fn event(&self, event: GenerationEvent) -> GenerationSignal {
+            if let GenerationEvent::SpoilerGroupFinalized { .. } = event {
+                self.groups.fetch_add(1, Ordering::Relaxed);
+            }
+            if self.groups.load(Ordering::Relaxed) >= self.limit {
+                GenerationSignal::Cancel
+            } else {
+                GenerationSignal::Continue
+            }
+        }
+    }
+
+    #[test]
+    fn cancellation() {
+        let universe_settings = UniverseSettings::default();
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+
+        let observer = CancelAfterGroups {
+            groups: AtomicUsize::new(0),
+            limit: 3,
+        };
+        let result = generate_seed_observed(&graph, &NO_FILE_ACCESS, &universe_settings, &observer);
+        assert!(matches!(result, Err(GenerationError::Cancelled)));
+        assert_eq!(observer.groups.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn parallel_attempts() {
+        let mut universe_settings = UniverseSettings::default();
+        universe_settings.tuning.parallel_attempts = true;
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+
+        let seed = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).unwrap();
+        let again = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).unwrap();
+        assert_eq!(seed.seed_files().unwrap(), again.seed_files().unwrap());
+    }
+
+    #[test]
+    fn door_randomization() {
+        let mut universe_settings = UniverseSettings::default();
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+
+        let seed = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).unwrap();
+        assert!(seed.spoiler.doors.is_empty());
+        assert!(!seed.seed_files().unwrap()[0].contains("// Doors: "));
+
+        universe_settings.door_randomization = true;
+        let err = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).err();
+        assert!(matches!(
+            err,
+            Some(GenerationError::Settings(SettingsError::ExperimentalDoors))
+        ));
+
+        universe_settings.tuning.experimental_doors = true;
+        let layouts = (0..3)
+            .map(|seed| {
+                universe_settings.seed = seed.to_string();
+                let seed = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).unwrap();
+                assert_eq!(seed.spoiler.doors.len(), 8);
+                assert!(seed.seed_files().unwrap()[0].contains("// Doors: "));
+                seed.spoiler
+                    .doors
+                    .iter()
+                    .map(|door| format!("{}>{}", door.from, door.to))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        // The same logic is shared between seeds, but each seed shuffles its own doors
+        assert!(layouts.iter().any(|layout| layout != &layouts[0]));
+    }
+
+    #[test]
+    fn placement_constraints() {
+        let mut universe_settings = UniverseSettings::default();
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+
+        let launch = Item::Skill(Skill::Launch);
+        universe_settings.world_settings[0].placement_constraints = vec![PlacementConstraint {
+            item: launch.clone(),
+            mode: ConstraintMode::Only,
+            locations: vec![LocationFilter::Zone(Zone::Marsh)],
+        }];
+        for seed in 0..5 {
+            universe_settings.seed = seed.to_string();
+            let seed = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).unwrap();
+            let placement = seed.worlds[0]
+                .placements
+                .iter()
+                .find(|placement| placement.item == launch)
+                .unwrap();
+            assert_eq!(placement.node.and_then(Node::zone), Some(Zone::Marsh));
+        }
+
+        universe_settings.world_settings[0].placement_constraints[0].locations =
+            vec![LocationFilter::Zone(Zone::Shop)];
+        let seed = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).unwrap();
+        let placement = seed.worlds[0]
+            .placements
+            .iter()
+            .find(|placement| placement.item == launch)
+            .unwrap();
+        assert_eq!(placement.node.and_then(Node::zone), Some(Zone::Shop));
+
+        universe_settings.world_settings[0].placement_constraints[0].locations =
+            vec![LocationFilter::Shop];
+        for seed in 0..5 {
+            universe_settings.seed = seed.to_string();
+            let seed = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).unwrap();
+            let placement = seed.worlds[0]
+                .placements
+                .iter()
+                .find(|placement| placement.item == launch)
+                .unwrap();
+            assert!(placement.trigger.identifier.is_purchasable());
+        }
+
+        universe_settings.world_settings[0].placement_constraints[0].locations =
+            vec![LocationFilter::Node("Nowhere".to_string())];
+        let err = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).err();
+        assert!(matches!(
+            err,
+            Some(GenerationError::Settings(
+                SettingsError::UnknownConstraintLocation { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn hints() {
+        let mut universe_settings = UniverseSettings::default();
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+
+        universe_settings.world_settings[0].hints = HintSettings {
+            way_of_the_hero: 2,
+            barren: 1,
+            zone_counts: true,
+            triggers: vec!["48248|18767".to_string(), "48248|3638".to_string()],
+        };
+        let err = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).err();
+        assert!(matches!(
+            err,
+            Some(GenerationError::Settings(
+                SettingsError::HintsNeedRequiredItems { world_index: 0 }
+            ))
+        ));
+
+        universe_settings.required_items = true;
+        let seed = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).unwrap();
+        let hints = seed.worlds[0]
+            .placements
+            .iter()
+            .filter(|placement| placement.node.is_none())
+            .filter_map(|placement| match &placement.item {
+                Item::Message(message) => Some(message.message.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(hints.len(), 2);
+        // Way of the Hero hints come first, followed by barren hints and zone counts
+        let required_zones = seed.spoiler.required[0]
+            .iter()
+            .flat_map(|required| &required.placements)
+            .filter_map(|placement| placement.location.zone)
+            .collect::<Vec<_>>();
+        assert!(!required_zones.is_empty());
+        assert!(required_zones
+            .iter()
+            .any(|zone| hints[0].contains(&format!("{zone} is on the Way of the Hero"))));
+        assert!(!required_zones
+            .iter()
+            .any(|zone| hints[0].contains(&format!("Nothing required in {zone}"))));
+        assert!(hints[1].contains("Skills"));
+
+        universe_settings.world_settings[0].hints.triggers = vec!["nonsense".to_string()];
+        let err = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).err();
+        assert!(matches!(
+            err,
+            Some(GenerationError::Settings(
+                SettingsError::InvalidHintTrigger { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn custom_item_pool() {
+        let mut universe_settings = UniverseSettings::default();
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+
+        let energy = Item::Resource(Resource::EnergyFragment);
+        universe_settings.world_settings[0].item_pool = ItemPoolSettings {
+            items: vec![ItemCount {
+                item: Item::Shard(Shard::Overcharge),
+                count: 0,
+            }],
+            spirit_light: Some(5000),
+            junk: JunkFill::Item(energy.clone()),
+            ..ItemPoolSettings::default()
+        };
+        let seed = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).unwrap();
+        let placements = &seed.worlds[0].placements;
+        assert!(!placements
+            .iter()
+            .any(|placement| placement.item == Item::Shard(Shard::Overcharge)));
+        assert!(
+            placements
+                .iter()
+                .filter(|placement| placement.item == energy)
+                .count()
+                > 24
+        );
+
+        universe_settings.world_settings[0].item_pool.items[0] = ItemCount {
+            item: Item::Resource(Resource::GorlekOre),
+            count: 1000,
+        };
+        let err = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).err();
+        assert!(matches!(
+            err,
+            Some(GenerationError::Settings(
+                SettingsError::ItemPoolTooLarge { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn custom_goals() {
+        let mut universe_settings = UniverseSettings::default();
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+
+        let goal_header = |content: &str| InlineHeader {
+            name: Some("goal".to_string()),
+            content: content.to_string(),
+        };
+
+        universe_settings.world_settings[0].inline_headers = vec![goal_header(
+            "!!goal 21786|60210, 21786|49485>=1 Marsh Pickups",
+        )];
+        let seed = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).unwrap();
+        assert!(seed.worlds[0]
+            .flags
+            .iter()
+            .any(|flag| flag == "Marsh Pickups"));
+        let seed_file = &seed.seed_files().unwrap()[0];
+        let check = "4|18|21786|60210|0|4|18|21786|49485|0|8|16|0|bool|true";
+        for line in [
+            "// Custom goal: Marsh Pickups".to_string(),
+            format!("21786|60210|{check}"),
+            format!("21786|49485|{check}"),
+            format!("3|1|{check}"),
+        ] {
+            assert!(
+                seed_file.lines().any(|seed_line| seed_line == line),
+                "{line}"
+            );
+        }
+
+        universe_settings.world_settings[0].goals =
+            GoalModes::from_iter([Goal::Custom(CustomGoal {
+                flag: "Marsh Pickups".to_string(),
+                conditions: vec![],
+            })]);
+        let err = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).err();
+        assert!(matches!(
+            err,
+            Some(GenerationError::Settings(
+                SettingsError::ContradictingGoals { world_index: 0, .. }
+            ))
+        ));
+
+        universe_settings.world_settings[0].goals = GoalModes::default();
+        universe_settings.world_settings[0].inline_headers =
+            vec![goal_header("!!goal 21786|60210=5 Impossible")];
+        let err = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).err();
+        assert!(matches!(
+            err,
+            Some(GenerationError::Logic(LogicError::UnreachableGoal { ref goal, .. })) if goal == "Impossible"
+        ));
+    }
+}
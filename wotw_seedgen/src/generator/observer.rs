@@ -0,0 +1,91 @@
+use crate::{world::graph::Node, Inventory};
+
+use super::{error::GenerationError, spoiler::SpoilerGroup};
+
+/// Receives [`GenerationEvent`]s while a seed is being generated
+///
+/// Every method has a default implementation, so implementors only need to override what they are interested in
+///
+/// Observers have to be [`Sync`] since [`GenerationTuning::parallel_attempts`](crate::settings::GenerationTuning::parallel_attempts) may notify them from multiple threads
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::generator::{GenerationEvent, GenerationObserver, GenerationSignal};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// /// Cancels generation after too many retries
+/// struct Impatient {
+///     retries: AtomicUsize,
+/// }
+/// impl GenerationObserver for Impatient {
+///     fn event(&self, event: GenerationEvent) -> GenerationSignal {
+///         if let GenerationEvent::RetryStarted { .. } = event {
+///             if self.retries.fetch_add(1, Ordering::Relaxed) >= 2 {
+///                 return GenerationSignal::Cancel;
+///             }
+///         }
+///         GenerationSignal::Continue
+///     }
+/// }
+/// ```
+pub trait GenerationObserver: Sync {
+    /// Called for every [`GenerationEvent`]
+    ///
+    /// Returning [`GenerationSignal::Cancel`] will stop the generation at the next opportunity with [`GenerationError::Cancelled`]
+    fn event(&self, event: GenerationEvent) -> GenerationSignal {
+        let _ = event;
+        GenerationSignal::Continue
+    }
+    /// Polled between iterations of the placement loop
+    ///
+    /// Returning `true` will stop the generation with [`GenerationError::Cancelled`]
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+/// A [`GenerationObserver`] implementation that ignores all events and never cancels
+pub struct NoObserver;
+impl GenerationObserver for NoObserver {}
+/// Instance of [`NoObserver`]
+pub const NO_OBSERVER: NoObserver = NoObserver;
+
+/// Whether generation should continue after a [`GenerationEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenerationSignal {
+    /// Keep generating
+    Continue,
+    /// Stop generating as soon as possible
+    Cancel,
+}
+
+/// Notable steps during seed generation
+#[derive(Debug, Clone, Copy)]
+pub enum GenerationEvent<'a> {
+    /// The spawn locations for an attempt were chosen
+    SpawnChosen {
+        /// Starting from 1
+        attempt: usize,
+        /// Spawn location per world
+        spawns: &'a [&'a Node],
+    },
+    /// A [`SpoilerGroup`] has been completed
+    SpoilerGroupFinalized {
+        /// Index of the [`SpoilerGroup`] in the [`SeedSpoiler`](super::SeedSpoiler)
+        index: usize,
+        group: &'a SpoilerGroup,
+    },
+    /// A set of items was chosen as forced progression
+    ForcedProgression {
+        world_index: usize,
+        progression: &'a Inventory,
+    },
+    /// The previous attempt failed and another one is about to start
+    RetryStarted {
+        /// Starting from 1, so the first retry is attempt 2
+        attempt: usize,
+        /// Why the previous attempt failed
+        reason: &'a GenerationError,
+    },
+}
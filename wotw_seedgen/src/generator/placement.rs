@@ -1,13 +1,22 @@
-use std::mem;
+use std::{
+    mem,
+    num::NonZeroUsize,
+    panic, ptr,
+    sync::atomic::{AtomicBool, AtomicU16, AtomicUsize, Ordering},
+    thread,
+};
 
 use rand::{
     distributions::{Bernoulli, Distribution, Uniform},
+    prelude::StdRng,
     seq::{IteratorRandom, SliceRandom},
     Rng,
 };
+use rand_seeder::Seeder;
 
 #[cfg(feature = "log")]
 use crate::settings::Difficulty;
+use crate::uber_state::{UberIdentifier, UberStateTrigger, UberType};
 use crate::{
     generator::spoiler::NodeSummary,
     header::CodeDisplay,
@@ -16,24 +25,19 @@ use crate::{
         Command, Item, Message, Resource, ShopCommand, Teleporter, UberStateItem, UberStateValue,
     },
     log,
-    settings::{Goal, Spawn, WorldSettings},
+    settings::{GenerationTuning, Goal, Spawn, WorldSettings},
     util::{
         self,
-        constants::{
-            DEFAULT_SPAWN, KEYSTONE_DOORS, PLACEHOLDER_SLOTS, RANDOM_PROGRESSION, RELIC_ZONES,
-            RESERVE_SLOTS, RETRIES, SHOP_PRICES,
-        },
+        constants::{DEFAULT_SPAWN, KEYSTONE_DOORS, MULTIWORLD_GROUP, RELIC_ZONES, SHOP_PRICES},
     },
     world::{
         graph::{self, Graph, Node},
         requirement, World,
     },
 };
-use crate::{
-    uber_state::{UberIdentifier, UberStateTrigger, UberType},
-    util::constants::{PREFERRED_SPAWN_SLOTS, SPAWN_SLOTS},
-};
 
+use super::error::{GenerationError, LogicError, PlacementError, SettingsError};
+use super::observer::{GenerationEvent, GenerationObserver, GenerationSignal};
 use super::seed::SeedWorld;
 use super::spoiler::{SeedSpoiler, SpoilerGroup, SpoilerPlacement};
 
@@ -84,6 +88,9 @@ where
     current_spoiler_group: SpoilerGroup,
     price_range: Uniform<f32>,
     random_progression: Bernoulli,
+    tuning: &'a GenerationTuning,
+    observer: &'a dyn GenerationObserver,
+    cancelled: bool,
     rng: &'a mut R,
 }
 
@@ -94,6 +101,26 @@ impl<R: Rng, I: Iterator<Item = u16>> GeneratorContext<'_, R, I> {
             .sort_unstable_by(|a, b| a.item.cmp(&b.item));
         self.spoiler_groups
             .push(mem::take(&mut self.current_spoiler_group));
+
+        let index = self.spoiler_groups.len() - 1;
+        let event = GenerationEvent::SpoilerGroupFinalized {
+            index,
+            group: &self.spoiler_groups[index],
+        };
+        if self.observer.event(event) == GenerationSignal::Cancel {
+            self.cancelled = true;
+        }
+    }
+
+    /// Passes the event on to the observer and remembers if it requested to cancel
+    fn notify(&mut self, event: GenerationEvent) {
+        if self.observer.event(event) == GenerationSignal::Cancel {
+            self.cancelled = true;
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled || self.observer.is_cancelled()
     }
 }
 
@@ -124,7 +151,7 @@ fn format_identifiers(mut identifiers: Vec<&str>) -> String {
 fn progression_check<'a, R, I>(
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<ReachContext<'a>, String>
+) -> Result<ReachContext<'a>, GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -179,11 +206,25 @@ fn place_item<'a, R, I>(
     item: Item,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
 {
+    if !world_contexts[target_world_index]
+        .world
+        .player
+        .settings
+        .allows_placement(&item, node)
+    {
+        return Err(PlacementError::ConstraintViolated {
+            world_index: target_world_index,
+            item,
+            node: node.identifier().to_string(),
+        }
+        .into());
+    }
+
     let trigger = node.trigger().unwrap();
     let is_shop = trigger.identifier.is_shop();
 
@@ -278,7 +319,7 @@ where
             "$[15|5|{}]'s {}",
             target_world_index, custom_name
         )));
-        let send_identifier = UberIdentifier::new(12, state_index);
+        let send_identifier = UberIdentifier::new(MULTIWORLD_GROUP, state_index);
         let send_item = UberStateItem::simple_setter(
             send_identifier,
             UberType::Bool,
@@ -290,7 +331,7 @@ where
             origin_world_index
         )));
         let target_trigger = UberStateTrigger {
-            identifier: UberIdentifier::new(12, state_index),
+            identifier: UberIdentifier::new(MULTIWORLD_GROUP, state_index),
             condition: None,
         };
 
@@ -350,7 +391,7 @@ fn shop_placement<'a, R, I>(
     target_world_index: usize,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -365,9 +406,13 @@ where
         .get(item);
     let trigger = node.trigger().unwrap();
 
-    let (_, _, price_uber_identifier) = SHOP_PRICES.iter()
+    let (_, _, price_uber_identifier) = SHOP_PRICES
+        .iter()
         .find(|(_, location, _)| &trigger.identifier == location)
-        .ok_or_else(|| format!("(World {}): {} claims to be a shop location, but doesn't have an entry in the shop prices table!", origin_world_index, node))?;
+        .ok_or_else(|| PlacementError::MissingShopPrice {
+            world_index: origin_world_index,
+            node: node.identifier().to_string(),
+        })?;
 
     let mut price = origin_details
         .and_then(|details| details.price)
@@ -376,12 +421,11 @@ where
 
     if item.random_shop_price() {
         let modified_price = price as f32 * context.price_range.sample(context.rng);
-        price = util::float_to_int(modified_price).map_err(|_| {
-            format!(
-                "(World {}): Overflowed shop price for {} after adding a random amount to it",
-                origin_world_index, item
-            )
-        })?;
+        price =
+            util::float_to_int(modified_price).map_err(|_| PlacementError::ShopPriceOverflow {
+                world_index: origin_world_index,
+                item: item.clone(),
+            })?;
     }
 
     let price_setter = UberStateItem::simple_setter(
@@ -447,12 +491,13 @@ fn place_relics<'a, R, I>(
     world_index: usize,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
 {
     let world_context = &mut world_contexts[world_index];
+    let settings = world_context.world.player.settings;
 
     let mut relic_zones = RELIC_ZONES.to_vec();
     relic_zones.shuffle(context.rng);
@@ -476,6 +521,7 @@ where
                         .placements
                         .iter()
                         .any(|placement| &placement.trigger == trigger)
+                    && settings.allows_placement(&Item::Relic(zone), node)
                 {
                     zone_relic_locations.push(node);
                 }
@@ -509,7 +555,7 @@ fn force_keystones<'a, R, I>(
     reserved_slots: &mut Vec<(usize, &'a Node)>,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -567,7 +613,7 @@ fn forced_placement<'a, R, I>(
     reserved_slots: &mut Vec<(usize, &'a Node)>,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -580,6 +626,7 @@ where
         item.is_multiworld_spread()
     };
 
+    let settings = world_contexts[target_world_index].world.player.settings;
     let mut choose_node = || {
         if is_multiworld_spread {
             let mut world_indices = (0..context.world_count).collect::<Vec<_>>();
@@ -622,28 +669,44 @@ where
                 false,
             ));
         }
-        return Err(format!(
-            "(World {}): Not enough slots to place forced progression {}",
-            target_world_index, item
-        )); // due to the slot checks in missing_items this should only ever happen for forced keystone placements
+        Err(PlacementError::NotEnoughSlots {
+            world_index: target_world_index,
+            item: item.clone(),
+        }) // due to the slot checks in missing_items this should only ever happen for forced keystone placements
     };
 
     let mut node = choose_node()?;
 
-    // Don't place Spirit Light in shops
-    if matches!(item, Item::SpiritLight(_)) {
-        let mut skipped_slots = Vec::new();
+    // Don't place Spirit Light in shops and respect the placement constraints
+    let mut skipped_slots = Vec::new();
 
-        while node.1.trigger().unwrap().identifier.is_purchasable() {
-            skipped_slots.push((node.0, node.1));
+    while (matches!(item, Item::SpiritLight(_))
+        && node.1.trigger().unwrap().identifier.is_purchasable())
+        || !settings.allows_placement(&item, node.1)
+    {
+        skipped_slots.push((node.0, node.1));
 
-            node = choose_node()?;
-        }
+        node = choose_node().map_err(|err| {
+            if skipped_slots
+                .iter()
+                .any(|&(_, skipped)| !settings.allows_placement(&item, skipped))
+            {
+                PlacementError::ConstraintUnsatisfiable {
+                    world_index: target_world_index,
+                    item: item.clone(),
+                }
+            } else {
+                err
+            }
+        })?;
+    }
 
-        for skipped_slot in skipped_slots {
-            world_contexts[skipped_slot.0]
-                .placeholders
-                .push(skipped_slot.1);
+    for (world_index, skipped) in skipped_slots {
+        let world_context = &mut world_contexts[world_index];
+        if ptr::eq(skipped, &world_context.world.graph.spawn_pickup_node) {
+            world_context.spawn_slots += 1;
+        } else {
+            world_context.placeholders.push(skipped);
         }
     }
 
@@ -710,7 +773,7 @@ fn pick_progression<'a, 'b, R, I>(
     reach_context: &ReachContext,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<&'b Inventory, String>
+) -> Result<&'b Inventory, GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -721,7 +784,8 @@ where
         itemsets.len()
     );
 
-    let weight = |inventory: &Inventory| -> Result<f32, String> {
+    let unpreferred_spawn_slots = context.tuning.spawn_slots - context.tuning.preferred_spawn_slots;
+    let weight = |inventory: &Inventory| -> Result<f32, GenerationError> {
         let target_world_context = &world_contexts[target_world_index];
 
         let mut lookahead_player = target_world_context.world.player.clone();
@@ -742,9 +806,8 @@ where
 
         let mut weight = 1.0 / inventory.cost() as f32 * (newly_reached + 1) as f32;
 
-        let begrudgingly_used_slots = (inventory.item_count() as usize
-            + (SPAWN_SLOTS - PREFERRED_SPAWN_SLOTS))
-            .saturating_sub(slots);
+        let begrudgingly_used_slots =
+            (inventory.item_count() as usize + unpreferred_spawn_slots).saturating_sub(slots);
         if begrudgingly_used_slots > 0 {
             weight *= (0.3_f32).powf(begrudgingly_used_slots as f32);
         }
@@ -753,7 +816,7 @@ where
     };
     let with_weights = itemsets
         .iter()
-        .map::<Result<(&Inventory, f32), String>, _>(|inventory| {
+        .map::<Result<(&Inventory, f32), GenerationError>, _>(|inventory| {
             Ok((inventory, weight(inventory)?))
         })
         .collect::<Result<Vec<_>, _>>()?;
@@ -772,11 +835,9 @@ where
 
             weight
         })
-        .map_err(|err| {
-            format!(
-                "(World {}): Error choosing progression: {}",
-                target_world_index, err
-            )
+        .map_err(|source| PlacementError::ChooseProgression {
+            world_index: target_world_index,
+            source,
         })?;
 
     log::trace!(
@@ -822,7 +883,7 @@ fn force_progression<'a, R, I>(
     reach_context: &mut ReachContext,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -889,48 +950,61 @@ where
                         world_context.spawn
                     );
                 }
-                return Err(String::from("Failed to reach anything from spawn location"));
+                return Err(PlacementError::NothingReachable {
+                    spawns: world_contexts
+                        .iter()
+                        .map(|world_context| world_context.spawn.identifier().to_string())
+                        .collect(),
+                }
+                .into());
             }
 
-            #[cfg(feature = "log")]
-            for (world_index, world_context) in world_contexts.iter().enumerate() {
-                let identifiers: Vec<_> = world_context
-                    .reachable_locations
-                    .iter()
-                    .filter_map(|&node| {
-                        let node_index = node.index();
+            let unreached = world_contexts
+                .iter()
+                .map(|world_context| {
+                    world_context
+                        .reachable_locations
+                        .iter()
+                        .filter_map(|&node| {
+                            let node_index = node.index();
 
-                        node.trigger().and_then(|uber_state| {
-                            if !world_context
-                                .placements
-                                .iter()
-                                .any(|placement| &placement.trigger == uber_state)
-                                && !world_context
-                                    .placeholders
+                            node.trigger().and_then(|uber_state| {
+                                if !world_context
+                                    .placements
                                     .iter()
-                                    .any(|&placeholder| placeholder.index() == node_index)
-                                && !world_context
-                                    .collected_preplacements
-                                    .iter()
-                                    .any(|&collected| collected == node_index)
-                            {
-                                Some(node.identifier())
-                            } else {
-                                None
-                            }
+                                    .any(|placement| &placement.trigger == uber_state)
+                                    && !world_context
+                                        .placeholders
+                                        .iter()
+                                        .any(|&placeholder| placeholder.index() == node_index)
+                                    && !world_context
+                                        .collected_preplacements
+                                        .iter()
+                                        .any(|&collected| collected == node_index)
+                                {
+                                    Some(node.identifier().to_string())
+                                } else {
+                                    None
+                                }
+                            })
                         })
-                    })
-                    .collect();
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
 
+            #[cfg(feature = "log")]
+            for (world_index, (world_context, identifiers)) in
+                world_contexts.iter().zip(&unreached).enumerate()
+            {
                 log::trace!(
                     "(World {}): Failed to reach all locations with inventory: {}\nUnreached locations: {}",
                     world_index,
                     world_context.world.player.inventory,
-                    format_identifiers(identifiers)
+                    format_identifiers(identifiers.iter().map(String::as_str).collect())
                 );
             }
 
-            return Err(String::from("Failed to reach all locations"));
+            return Err(PlacementError::UnreachedLocations { unreached }.into());
         }
     };
 
@@ -944,6 +1018,10 @@ where
         world_contexts,
         context,
     )?;
+    context.notify(GenerationEvent::ForcedProgression {
+        world_index: target_world_index,
+        progression,
+    });
 
     for (item, amount) in &progression.items {
         let items =
@@ -969,7 +1047,7 @@ fn random_item_placement<'a, R, I>(
     node: &'a Node,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<bool, String>
+) -> Result<bool, GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -997,12 +1075,13 @@ where
                     .item_count() as usize
         {
             let target_world_context = &mut world_contexts[target_world_index];
+            let settings = target_world_context.world.player.settings;
 
-            if let Some(item) = target_world_context
-                .world
-                .pool
-                .choose_random(origin_world_index != target_world_index, context.rng)
-            {
+            if let Some(item) = target_world_context.world.pool.choose_random(
+                origin_world_index != target_world_index,
+                |item| settings.allows_placement(item, node),
+                context.rng,
+            ) {
                 let item = item.clone();
                 let is_progression =
                     item.is_progression(target_world_context.world.player.settings.difficulty);
@@ -1059,7 +1138,7 @@ fn random_placement<'a, R, I>(
     allow_placeholder: bool,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<bool, String>
+) -> Result<bool, GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -1102,7 +1181,7 @@ where
 fn one_xp<'a, R, I>(
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -1160,10 +1239,16 @@ impl SpiritLightAmounts {
         random_low: f32,
         random_high: f32,
     ) -> SpiritLightAmounts {
-        let factor = (spirit_light_pool - spirit_light_slots * 50.0)
-            / (spirit_light_slots.powi(3) / 3.0
-                + spirit_light_slots.powi(2) / 2.0
-                + spirit_light_slots / 6.0);
+        // Custom item pools may leave no room for Spirit Light, any stray slots still get the base amount
+        let factor = if spirit_light_slots < 1.0 {
+            0.0
+        } else {
+            ((spirit_light_pool - spirit_light_slots * 50.0)
+                / (spirit_light_slots.powi(3) / 3.0
+                    + spirit_light_slots.powi(2) / 2.0
+                    + spirit_light_slots / 6.0))
+                .max(0.0)
+        };
         let noise = Uniform::new_inclusive(random_low, random_high);
 
         SpiritLightAmounts {
@@ -1186,7 +1271,7 @@ impl SpiritLightAmounts {
 fn place_remaining<'a, R, I>(
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -1227,6 +1312,7 @@ where
 
         remaining.shuffle(context.rng);
 
+        let settings = world_contexts[target_world_index].world.player.settings;
         let mut space_remaining = true;
         'outer: for item in remaining {
             if space_remaining {
@@ -1238,10 +1324,16 @@ where
                     vec![target_world_index]
                 };
 
-                for origin_world_index in origin_world_indices {
-                    if let Some(node) = shop_placeholders[origin_world_index]
-                        .pop()
-                        .or_else(|| world_contexts[origin_world_index].placeholders.pop())
+                for &origin_world_index in &origin_world_indices {
+                    if let Some(node) =
+                        take_allowed(&mut shop_placeholders[origin_world_index], settings, &item)
+                            .or_else(|| {
+                                take_allowed(
+                                    &mut world_contexts[origin_world_index].placeholders,
+                                    settings,
+                                    &item,
+                                )
+                            })
                     {
                         place_item(
                             origin_world_index,
@@ -1257,6 +1349,17 @@ where
                     }
                 }
 
+                if origin_world_indices.iter().any(|&origin_world_index| {
+                    !shop_placeholders[origin_world_index].is_empty()
+                        || !world_contexts[origin_world_index].placeholders.is_empty()
+                }) {
+                    return Err(PlacementError::ConstraintUnsatisfiable {
+                        world_index: target_world_index,
+                        item,
+                    }
+                    .into());
+                }
+
                 space_remaining = false;
 
                 log::warning!(
@@ -1347,8 +1450,23 @@ where
     Ok(())
 }
 
+/// Removes the last node that the [`PlacementConstraint`](crate::settings::PlacementConstraint)s allow for the item
+fn take_allowed<'a>(
+    nodes: &mut Vec<&'a Node>,
+    settings: &WorldSettings,
+    item: &Item,
+) -> Option<&'a Node> {
+    let index = nodes
+        .iter()
+        .rposition(|&node| settings.allows_placement(item, node))?;
+    Some(nodes.remove(index))
+}
+
 #[inline]
-fn total_reach_check<'a>(world: &World<'a, '_>) -> Result<Vec<&'a Node>, String> {
+fn total_reach_check<'a>(
+    world_index: usize,
+    world: &World<'a, '_>,
+) -> Result<Vec<&'a Node>, LogicError> {
     let mut finished_world = world.clone();
     for (item, amount) in &world.pool.inventory.items {
         if item.is_progression(world.player.settings.difficulty) {
@@ -1360,7 +1478,13 @@ fn total_reach_check<'a>(world: &World<'a, '_>) -> Result<Vec<&'a Node>, String>
     let mut collected_preplacements = Vec::new();
     let mut total_reachable_count = 0;
 
-    let spawn = finished_world.graph.find_spawn(DEFAULT_SPAWN)?;
+    let spawn = finished_world
+        .graph
+        .find_spawn(DEFAULT_SPAWN)
+        .map_err(|message| LogicError::DefaultSpawn {
+            world_index,
+            message,
+        })?;
 
     loop {
         let mut reachable_locations = finished_world.graph.reached_locations(
@@ -1374,6 +1498,17 @@ fn total_reach_check<'a>(world: &World<'a, '_>) -> Result<Vec<&'a Node>, String>
         if new_reachable_count > total_reachable_count {
             total_reachable_count = new_reachable_count;
         } else {
+            for goal in &world.goals {
+                if let Goal::Custom(custom) = goal {
+                    if !custom.is_met(|identifier| finished_world.get_uber_state(identifier)) {
+                        return Err(LogicError::UnreachableGoal {
+                            world_index,
+                            goal: custom.flag.clone(),
+                        });
+                    }
+                }
+            }
+
             reachable_locations.retain(|&node| node.can_place());
             return Ok(reachable_locations);
         }
@@ -1403,7 +1538,7 @@ fn total_reach_check<'a>(world: &World<'a, '_>) -> Result<Vec<&'a Node>, String>
 fn flush_item_pool<'a, R, I>(
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -1421,12 +1556,18 @@ where
             .flat_map(|(item, amount)| vec![item.clone(); *amount as usize])
             .collect::<Vec<_>>();
 
+        let settings = world_contexts[target_world_index].world.player.settings;
+
         'outer: for item in uber_state_items {
             let mut origin_world_indices = (0..context.world_count).collect::<Vec<_>>();
             origin_world_indices.shuffle(context.rng);
 
             for origin_world_index in origin_world_indices {
-                if let Some(node) = world_contexts[origin_world_index].placeholders.pop() {
+                if let Some(node) = take_allowed(
+                    &mut world_contexts[origin_world_index].placeholders,
+                    settings,
+                    &item,
+                ) {
                     let target_world_context = &mut world_contexts[target_world_index];
 
                     target_world_context.world.pool.remove(&item, 1);
@@ -1454,12 +1595,15 @@ where
 fn generate_placements_from_spawn<'graph, 'settings>(
     worlds: Vec<World<'graph, 'settings>>,
     spawns: Vec<&'graph Node>,
+    tuning: &GenerationTuning,
+    observer: &dyn GenerationObserver,
     rng: &mut impl Rng,
-) -> Result<(Vec<SeedWorld<'graph, 'settings>>, SeedSpoiler), String> {
+) -> Result<(Vec<SeedWorld<'graph, 'settings>>, SeedSpoiler), GenerationError> {
     // TODO enforce a max total price for shops
-    let price_range = Uniform::new_inclusive(0.75, 1.25);
+    let (price_low, price_high) = tuning.shop_price_range;
+    let price_range = Uniform::new_inclusive(price_low, price_high);
 
-    let mut world_contexts = build_world_contexts(worlds, &spawns, rng)?;
+    let mut world_contexts = build_world_contexts(worlds, &spawns, tuning.spawn_slots, rng)?;
 
     let total_reachable_count: usize = world_contexts
         .iter()
@@ -1473,7 +1617,10 @@ fn generate_placements_from_spawn<'graph, 'settings>(
         spoiler_groups: Vec::new(),
         current_spoiler_group: SpoilerGroup::default(),
         price_range,
-        random_progression: Bernoulli::new(RANDOM_PROGRESSION).unwrap(),
+        random_progression: Bernoulli::new(tuning.random_progression).unwrap(),
+        tuning,
+        observer,
+        cancelled: false,
         rng,
     };
 
@@ -1484,11 +1631,15 @@ fn generate_placements_from_spawn<'graph, 'settings>(
         }
     }
 
-    let mut reserved_slots = Vec::<(usize, &Node)>::with_capacity(RESERVE_SLOTS);
+    let mut reserved_slots = Vec::<(usize, &Node)>::with_capacity(tuning.reserve_slots);
 
     loop {
         context.finalize_spoiler_group();
 
+        if context.is_cancelled() {
+            return Err(GenerationError::Cancelled);
+        }
+
         let mut reach_context = progression_check(&mut world_contexts, &mut context)?;
 
         force_keystones(
@@ -1615,18 +1766,23 @@ fn generate_placements_from_spawn<'graph, 'settings>(
                 .unzip();
             let groups = context.spoiler_groups;
 
-            let spoiler = SeedSpoiler { spawns, groups };
+            let spoiler = SeedSpoiler {
+                spawns,
+                groups,
+                required: Vec::new(), // Filled later
+                doors: Vec::new(),    // Filled later
+            };
             return Ok((seed_worlds, spoiler));
         }
 
-        if reserved_slots.len() < RESERVE_SLOTS {
+        if reserved_slots.len() < tuning.reserve_slots {
             loop {
                 let world_index = context.rng.gen_range(0..context.world_count);
 
                 if let Some(node) = needs_placement[world_index].pop() {
                     reserved_slots.push((world_index, node));
 
-                    if reserved_slots.len() == RESERVE_SLOTS {
+                    if reserved_slots.len() == tuning.reserve_slots {
                         break;
                     }
                 } else {
@@ -1653,7 +1809,7 @@ fn generate_placements_from_spawn<'graph, 'settings>(
 
             for (origin_world_index, world_needs_placement) in needs_placement.iter().enumerate() {
                 for &node in world_needs_placement {
-                    let allow_placeholder = total_placeholders < PLACEHOLDER_SLOTS;
+                    let allow_placeholder = total_placeholders < tuning.placeholder_slots;
                     if random_placement(
                         origin_world_index,
                         node,
@@ -1685,8 +1841,9 @@ fn generate_placements_from_spawn<'graph, 'settings>(
 fn build_world_contexts<'a, 'b>(
     worlds: Vec<World<'a, 'b>>,
     spawns: &[&'a Node],
+    spawn_slots: usize,
     rng: &mut impl Rng,
-) -> Result<Vec<WorldContext<'a, 'b>>, String> {
+) -> Result<Vec<WorldContext<'a, 'b>>, GenerationError> {
     let mut has_warned_about_tp_refill = false;
 
     worlds.into_iter().enumerate().map(|(world_index, mut world)| {
@@ -1705,7 +1862,7 @@ fn build_world_contexts<'a, 'b>(
                 trigger: UberStateTrigger::spawn(),
                 item: Item::Message(message),
             });
-            SPAWN_SLOTS
+            spawn_slots
         };
 
         let mut spawn_is_tp = false;
@@ -1752,7 +1909,7 @@ fn build_world_contexts<'a, 'b>(
                     item,
                 });
             } else {
-                return Err(format!("Cannot spawn on {} which has no specified coordinates", spawn.identifier()));
+                return Err(LogicError::SpawnWithoutPosition { world_index, spawn: spawn.identifier().to_string() }.into());
             }
         }
 
@@ -1772,7 +1929,7 @@ fn build_world_contexts<'a, 'b>(
             }
         );
 
-        let reachable_locations = total_reach_check(&world)?;
+        let reachable_locations = total_reach_check(world_index, &world)?;
 
         let unreachable_locations = world.graph.nodes.iter()
             .filter(|&node|
@@ -1796,7 +1953,7 @@ fn build_world_contexts<'a, 'b>(
             })
             .count() - 1;  // 1 will be 1xp
         let mut spirit_light_slots = world_slots.saturating_sub(world.pool.inventory.item_count() as usize);
-        if let Some(amount) = world_tour { spirit_light_slots -= amount; }
+        if let Some(amount) = world_tour { spirit_light_slots = spirit_light_slots.saturating_sub(amount); }
         log::trace!("(World {}): Estimated {}/{} slots for Spirit Light", world_index, spirit_light_slots, world_slots);
 
         let spirit_light_rng = SpiritLightAmounts::new(world.pool.spirit_light as f32, spirit_light_slots as f32, 0.75, 1.25);
@@ -1823,14 +1980,15 @@ fn build_world_contexts<'a, 'b>(
             shop_slots,
             world_tour,
         })
-    }).collect::<Result<Vec<_>, String>>()
+    }).collect::<Result<Vec<_>, GenerationError>>()
 }
 
 fn pick_spawn<'a>(
+    world_index: usize,
     graph: &'a Graph,
     world_settings: &WorldSettings,
     rng: &mut impl Rng,
-) -> Result<&'a Node, String> {
+) -> Result<&'a Node, SettingsError> {
     let spawn = match &world_settings.spawn {
         Spawn::Random => {
             let spawns = world_settings.difficulty.spawn_locations();
@@ -1839,69 +1997,285 @@ fn pick_spawn<'a>(
                 .iter()
                 .filter(|&node| spawns.contains(&node.identifier()))
                 .choose(rng)
-                .ok_or_else(|| String::from("No valid spawn locations available"))?
+                .ok_or(SettingsError::NoSpawnLocations { world_index })?
         }
         Spawn::FullyRandom => graph
             .nodes
             .iter()
             .filter(|node| node.can_spawn())
             .choose(rng)
-            .ok_or_else(|| String::from("No valid spawn locations available"))?,
-        Spawn::Set(spawn_loc) => {
-            let node = graph
-                .nodes
+            .ok_or(SettingsError::NoSpawnLocations { world_index })?,
+        Spawn::Set(spawn_loc) => find_spawn(world_index, graph, spawn_loc)?,
+        Spawn::OneOf(spawn_locs) => {
+            let spawns = spawn_locs
                 .iter()
-                .find(|&node| node.identifier() == spawn_loc)
-                .ok_or_else(|| format!("Spawn {} not found", spawn_loc))?;
-            if !node.can_spawn() {
-                return Err(format!("{} is not a valid spawn", spawn_loc));
-            }
-            node
+                .map(|spawn_loc| find_spawn(world_index, graph, spawn_loc))
+                .collect::<Result<Vec<_>, _>>()?;
+            spawns
+                .choose(rng)
+                .copied()
+                .ok_or(SettingsError::NoSpawnLocations { world_index })?
+        }
+        Spawn::Weighted(spawn_locs) => {
+            let spawns = spawn_locs
+                .iter()
+                .map(|(spawn_loc, weight)| {
+                    find_spawn(world_index, graph, spawn_loc).map(|node| (node, *weight))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            spawns
+                .choose_weighted(rng, |(_, weight)| *weight)
+                .map_err(|_| SettingsError::NoSpawnLocations { world_index })?
+                .0
         }
     };
     Ok(spawn)
 }
 
+fn find_spawn<'a>(
+    world_index: usize,
+    graph: &'a Graph,
+    spawn_loc: &str,
+) -> Result<&'a Node, SettingsError> {
+    let node = graph
+        .nodes
+        .iter()
+        .find(|&node| node.identifier() == spawn_loc)
+        .ok_or_else(|| SettingsError::SpawnNotFound {
+            world_index,
+            spawn: spawn_loc.to_string(),
+        })?;
+    if !node.can_spawn() {
+        return Err(SettingsError::InvalidSpawn {
+            world_index,
+            spawn: spawn_loc.to_string(),
+        });
+    }
+    Ok(node)
+}
+
+fn pick_spawns<'graph>(
+    graph: &'graph Graph,
+    worlds: &[World],
+    rng: &mut impl Rng,
+) -> Result<Vec<&'graph Node>, SettingsError> {
+    let spawn_locs = worlds
+        .iter()
+        .enumerate()
+        .map(|(world_index, world)| pick_spawn(world_index, graph, world.player.settings, rng))
+        .collect::<Result<Vec<_>, SettingsError>>()?;
+    #[cfg(feature = "log")]
+    let identifiers = spawn_locs
+        .iter()
+        .map(|spawn_loc| spawn_loc.identifier())
+        .collect::<Vec<_>>();
+    log::trace!("Spawning on {}", identifiers.join(", "));
+
+    Ok(spawn_locs)
+}
+
 pub(super) fn generate_placements<'graph, 'settings>(
     graph: &'graph Graph,
     worlds: &[World<'graph, 'settings>],
+    tuning: &GenerationTuning,
+    observer: &impl GenerationObserver,
     rng: &mut impl Rng,
-) -> Result<(Vec<SeedWorld<'graph, 'settings>>, SeedSpoiler), String> {
-    let mut index = 0;
+) -> Result<(Vec<SeedWorld<'graph, 'settings>>, SeedSpoiler), GenerationError> {
+    let retries = usize::from(tuning.retries);
+    let mut attempts = Vec::with_capacity(retries);
     loop {
-        let spawn_locs = worlds
-            .iter()
-            .map(|world| pick_spawn(graph, world.player.settings, rng))
-            .collect::<Result<Vec<_>, String>>()?;
-        #[cfg(feature = "log")]
-        let identifiers = spawn_locs
-            .iter()
-            .map(|spawn_loc| spawn_loc.identifier())
-            .collect::<Vec<_>>();
-        log::trace!("Spawning on {}", identifiers.join(", "));
+        let spawn_locs = pick_spawns(graph, worlds, rng)?;
+
+        let event = GenerationEvent::SpawnChosen {
+            attempt: attempts.len() + 1,
+            spawns: &spawn_locs,
+        };
+        if observer.event(event) == GenerationSignal::Cancel || observer.is_cancelled() {
+            return Err(GenerationError::Cancelled);
+        }
 
-        match generate_placements_from_spawn(worlds.to_owned(), spawn_locs, rng) {
+        match generate_placements_from_spawn(worlds.to_owned(), spawn_locs, tuning, observer, rng) {
             Ok(seed) => {
+                let index = attempts.len();
                 if index > 0 {
                     log::info!(
                         "Generated seed after {} tries{}",
                         index + 1,
-                        if index < RETRIES / 2 { "" } else { " (phew)" }
+                        if index < retries / 2 { "" } else { " (phew)" }
                     );
                 }
 
                 return Ok(seed);
             }
-            #[cfg_attr(not(feature = "log"), allow(unused_variables))]
-            Err(err) => log::warning!("{}\nRetrying...", err),
+            Err(GenerationError::Cancelled) => return Err(GenerationError::Cancelled),
+            // The total reach check doesn't depend on the random placements, retrying won't help
+            Err(err @ GenerationError::Logic(LogicError::UnreachableGoal { .. })) => {
+                return Err(err)
+            }
+            Err(err) => {
+                log::warning!("{}\nRetrying...", err);
+                attempts.push(err);
+            }
         }
 
-        index += 1;
-        if index == RETRIES {
-            return Err(format!(
-                "All {} attempts to generate a seed failed :(",
-                RETRIES
-            ));
+        if attempts.len() == retries {
+            return Err(GenerationError::RetriesExhausted { attempts });
         }
+
+        let event = GenerationEvent::RetryStarted {
+            attempt: attempts.len() + 1,
+            reason: attempts.last().unwrap(),
+        };
+        if observer.event(event) == GenerationSignal::Cancel {
+            return Err(GenerationError::Cancelled);
+        }
+    }
+}
+
+/// Wraps the user's [`GenerationObserver`] for one attempt of [`generate_placements_parallel`]
+///
+/// The attempt gets cancelled if the user cancels or an attempt with a lower index already succeeded
+struct AttemptObserver<'a> {
+    observer: &'a dyn GenerationObserver,
+    index: u16,
+    first_success: &'a AtomicU16,
+    cancelled: &'a AtomicBool,
+}
+impl GenerationObserver for AttemptObserver<'_> {
+    fn event(&self, event: GenerationEvent) -> GenerationSignal {
+        let signal = self.observer.event(event);
+        if signal == GenerationSignal::Cancel {
+            self.cancelled.store(true, Ordering::Relaxed);
+        }
+        signal
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self.first_success.load(Ordering::Relaxed) < self.index
+            || self.observer.is_cancelled()
     }
 }
+
+type PlacementResult<'graph, 'settings> =
+    Result<(Vec<SeedWorld<'graph, 'settings>>, SeedSpoiler), GenerationError>;
+
+/// Runs the attempts of [`generate_placements`] on multiple threads
+///
+/// Each attempt uses its own rng derived from the seed and the attempt index.
+/// Out of all successful attempts, the one with the lowest index is used, so the result does not depend on the number of threads.
+///
+/// Since attempts don't run in sequence, [`GenerationEvent::RetryStarted`] is not emitted and events from different attempts may interleave.
+pub(super) fn generate_placements_parallel<'graph, 'settings>(
+    graph: &'graph Graph,
+    worlds: &[World<'graph, 'settings>],
+    seed: &str,
+    tuning: &GenerationTuning,
+    observer: &dyn GenerationObserver,
+) -> PlacementResult<'graph, 'settings> {
+    let retries = tuning.retries;
+    let threads = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(usize::from(retries));
+
+    let next_attempt = AtomicUsize::new(0);
+    let first_success = AtomicU16::new(u16::MAX);
+    let cancelled = AtomicBool::new(false);
+
+    let mut results = thread::scope(|scope| {
+        // we collect to spawn all the threads
+        #[allow(clippy::needless_collect)]
+        let handles = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = vec![];
+
+                    loop {
+                        let index = next_attempt.fetch_add(1, Ordering::Relaxed);
+                        if index >= usize::from(retries) {
+                            break;
+                        }
+                        let index = u16::try_from(index).unwrap();
+                        if index > first_success.load(Ordering::Relaxed)
+                            || cancelled.load(Ordering::Relaxed)
+                        {
+                            break;
+                        }
+
+                        let attempt_observer = AttemptObserver {
+                            observer,
+                            index,
+                            first_success: &first_success,
+                            cancelled: &cancelled,
+                        };
+                        let result =
+                            generate_attempt(graph, worlds, seed, index, tuning, &attempt_observer);
+                        if result.is_ok() {
+                            first_success.fetch_min(index, Ordering::Relaxed);
+                        }
+                        results.push((index, result));
+                    }
+
+                    results
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|err| panic::resume_unwind(err))
+            })
+            .collect::<Vec<_>>()
+    });
+    results.sort_unstable_by_key(|(index, _)| *index);
+
+    let mut attempts = Vec::with_capacity(results.len());
+    for (index, result) in results {
+        match result {
+            Ok(seed) => {
+                if index > 0 {
+                    log::info!("Generated seed on attempt {}", index + 1);
+                }
+
+                return Ok(seed);
+            }
+            // Attempts are only cancelled for superseding successes if they have a higher index, so this was the user
+            Err(GenerationError::Cancelled) => return Err(GenerationError::Cancelled),
+            Err(err @ GenerationError::Logic(LogicError::UnreachableGoal { .. })) => {
+                return Err(err)
+            }
+            Err(err) => {
+                log::warning!("Attempt {} failed: {}", index + 1, err);
+                attempts.push(err);
+            }
+        }
+    }
+
+    Err(GenerationError::RetriesExhausted { attempts })
+}
+
+fn generate_attempt<'graph, 'settings>(
+    graph: &'graph Graph,
+    worlds: &[World<'graph, 'settings>],
+    seed: &str,
+    index: u16,
+    tuning: &GenerationTuning,
+    observer: &AttemptObserver,
+) -> PlacementResult<'graph, 'settings> {
+    let mut rng: StdRng = Seeder::from((seed, index)).make_rng();
+
+    let spawn_locs = pick_spawns(graph, worlds, &mut rng)?;
+
+    let event = GenerationEvent::SpawnChosen {
+        attempt: usize::from(index) + 1,
+        spawns: &spawn_locs,
+    };
+    if observer.event(event) == GenerationSignal::Cancel || observer.is_cancelled() {
+        return Err(GenerationError::Cancelled);
+    }
+
+    generate_placements_from_spawn(worlds.to_owned(), spawn_locs, tuning, observer, &mut rng)
+}
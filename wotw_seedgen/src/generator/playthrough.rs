@@ -0,0 +1,356 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    item::Item,
+    log,
+    settings::Goal,
+    uber_state::{UberIdentifier, UberStateTrigger},
+    util::{
+        constants::{FINISH_ANCHOR, TREE_GROUP, WISP_STATES},
+        NodeKind,
+    },
+    world::{graph::Node, World},
+};
+
+use super::spoiler::{RequiredPlacements, SeedSpoiler, SpoilerPlacement};
+
+/// The state after collecting everything reachable
+struct Playthrough<'graph, 'settings> {
+    worlds: Vec<World<'graph, 'settings>>,
+    /// Indices of the reached [`Node`]s per world
+    reached: Vec<FxHashSet<usize>>,
+    /// Whether each placement was collected
+    collected: Vec<bool>,
+}
+
+/// Placements and where to find them
+struct Replay<'a, 'graph, 'settings> {
+    worlds: &'a [World<'graph, 'settings>],
+    spawns: &'a [&'graph Node],
+    placements: Vec<&'a SpoilerPlacement>,
+    /// Indices into `placements` by location identifier, per origin world
+    locations: Vec<FxHashMap<&'a str, Vec<usize>>>,
+}
+
+impl<'a, 'graph, 'settings> Replay<'a, 'graph, 'settings> {
+    fn new(
+        worlds: &'a [World<'graph, 'settings>],
+        spawns: &'a [&'graph Node],
+        spoiler: &'a SeedSpoiler,
+    ) -> Self {
+        let placements = spoiler
+            .groups
+            .iter()
+            .flat_map(|group| &group.placements)
+            .collect::<Vec<_>>();
+
+        let mut locations = vec![FxHashMap::<_, Vec<_>>::default(); worlds.len()];
+        for (index, placement) in placements.iter().enumerate() {
+            locations[placement.origin_world_index]
+                .entry(&placement.location.identifier[..])
+                .or_default()
+                .push(index);
+        }
+
+        Replay {
+            worlds,
+            spawns,
+            placements,
+            locations,
+        }
+    }
+
+    /// Collects everything reachable, skipping the `excluded` placements
+    ///
+    /// Placements on the spawn and items preplaced on spawn are collected first, same as during generation
+    fn play(&self, excluded: &[bool]) -> Playthrough<'graph, 'settings> {
+        let mut worlds = self.worlds.to_vec();
+        let mut reached = vec![FxHashSet::default(); worlds.len()];
+        let mut collected = vec![false; self.placements.len()];
+
+        for world_index in 0..worlds.len() {
+            worlds[world_index].set_uber_state(UberIdentifier::spawn(), 1.);
+            let spawn_location = worlds[world_index].graph.spawn_pickup_node.identifier();
+            self.collect(
+                world_index,
+                spawn_location,
+                excluded,
+                &mut worlds,
+                &mut collected,
+            );
+        }
+
+        loop {
+            let mut progress = false;
+
+            for world_index in 0..worlds.len() {
+                let world = &worlds[world_index];
+                let mut new_reached = world
+                    .graph
+                    .reached_locations(
+                        &world.player,
+                        self.spawns[world_index],
+                        world.uber_states(),
+                        &world.sets,
+                    )
+                    .into_iter()
+                    .filter(|node| reached[world_index].insert(node.index()))
+                    .collect::<Vec<_>>();
+                if new_reached.is_empty() {
+                    continue;
+                }
+                progress = true;
+
+                // Quest steps have to be set in order, same as during generation
+                new_reached.sort_unstable_by_key(|node| {
+                    node.trigger().map_or(0, UberStateTrigger::set_value)
+                });
+
+                for node in new_reached {
+                    if let Some(trigger) = node.trigger() {
+                        let value = trigger.set_value() as f32;
+                        worlds[world_index].set_incremental_uber_state(trigger.identifier, value);
+                    }
+
+                    self.collect(
+                        world_index,
+                        node.identifier(),
+                        excluded,
+                        &mut worlds,
+                        &mut collected,
+                    );
+                }
+            }
+
+            if !progress {
+                // Anchors aren't part of the reached locations, but the final fight is checked through its anchor
+                for ((world, reached), spawn) in worlds.iter().zip(&mut reached).zip(self.spawns) {
+                    let anchors = world.graph.reached_anchors(
+                        &world.player,
+                        spawn,
+                        world.uber_states(),
+                        &world.sets,
+                    );
+                    reached.extend(anchors.into_iter().map(Node::index));
+                }
+                return Playthrough {
+                    worlds,
+                    reached,
+                    collected,
+                };
+            }
+        }
+    }
+
+    /// Grants the placements at the location of the world
+    fn collect(
+        &self,
+        world_index: usize,
+        location: &str,
+        excluded: &[bool],
+        worlds: &mut [World],
+        collected: &mut [bool],
+    ) {
+        if let Some(indices) = self.locations[world_index].get(location) {
+            for &index in indices {
+                if !excluded[index] {
+                    let placement = self.placements[index];
+                    worlds[placement.target_world_index].grant_player(placement.item.clone(), 1);
+                    collected[index] = true;
+                }
+            }
+        }
+    }
+
+    /// Checks whether the [`Goal`] has been achieved, `None` meaning the final fight has been reached
+    fn goal_met(&self, goal: Option<&Goal>, world_index: usize, playthrough: &Playthrough) -> bool {
+        let world = &playthrough.worlds[world_index];
+        let reached = &playthrough.reached[world_index];
+
+        location_goal_met(goal, world, reached).unwrap_or_else(|| {
+            self.placements
+                .iter()
+                .zip(&playthrough.collected)
+                .filter(|(placement, _)| {
+                    placement.target_world_index == world_index
+                        && matches!(placement.item, Item::Relic(_))
+                })
+                .all(|(_, &collected)| collected)
+        })
+    }
+}
+
+/// Checks whether the [`Goal`] has been achieved based on the reached [`Node`] indices, `None` meaning the final fight has been reached
+///
+/// Returns `None` for Relic goals, since they depend on where the Relics were placed
+pub(crate) fn location_goal_met(
+    goal: Option<&Goal>,
+    world: &World,
+    reached: &FxHashSet<usize>,
+) -> Option<bool> {
+    let mut nodes = world.graph.nodes.iter();
+
+    let met = match goal {
+        None => {
+            nodes.any(|node| node.identifier() == FINISH_ANCHOR && reached.contains(&node.index()))
+        }
+        Some(Goal::Wisps) => WISP_STATES
+            .iter()
+            .all(|&identifier| world.get_uber_state(identifier) > 0.),
+        Some(Goal::Trees) => nodes
+            .filter(|node| {
+                node.trigger()
+                    .is_some_and(|trigger| trigger.identifier.uber_group == TREE_GROUP)
+            })
+            .all(|node| reached.contains(&node.index())),
+        Some(Goal::Quests) => nodes
+            .filter(|node| node.node_kind() == NodeKind::Quest)
+            .all(|node| reached.contains(&node.index())),
+        Some(Goal::Custom(goal)) => goal.is_met(|identifier| world.get_uber_state(identifier)),
+        Some(Goal::Relics(_) | Goal::RelicChance(_)) => return None,
+    };
+
+    Some(met)
+}
+
+/// Whether removing the placement could possibly prevent finishing
+fn is_candidate(placement: &SpoilerPlacement, world: &World) -> bool {
+    match placement.item {
+        Item::SpiritLight(_) => false,
+        Item::Relic(_) | Item::UberState(_) => true,
+        ref item => item.is_progression(world.player.settings.difficulty),
+    }
+}
+
+/// Finds a minimal set of placements needed for every goal of every world by replaying the seed and removing placements one by one
+///
+/// Goals that the replay doesn't achieve are reported with [`RequiredPlacements::met`] set to `false`
+///
+/// `worlds` should be in the state before any items were placed
+pub(super) fn required_placements(
+    worlds: &[World],
+    spawns: &[&Node],
+    spoiler: &SeedSpoiler,
+) -> Vec<Vec<RequiredPlacements>> {
+    let replay = Replay::new(worlds, spawns, spoiler);
+    let full = replay.play(&vec![false; replay.placements.len()]);
+
+    let candidates = replay
+        .placements
+        .iter()
+        .enumerate()
+        .filter(|&(index, placement)| {
+            full.collected[index] && is_candidate(placement, &worlds[placement.target_world_index])
+        })
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+
+    worlds
+        .iter()
+        .enumerate()
+        .map(|(world_index, world)| {
+            [None]
+                .into_iter()
+                .chain(world.goals.iter().map(Some))
+                .map(|goal| {
+                    if !replay.goal_met(goal, world_index, &full) {
+                        log::warning!(
+                            "(World {}): {} was not achieved when replaying the seed",
+                            world_index,
+                            goal.map_or("The final fight".to_string(), Goal::to_string)
+                        );
+                        return RequiredPlacements {
+                            goal: goal.cloned(),
+                            met: false,
+                            placements: vec![],
+                        };
+                    }
+
+                    let mut excluded = vec![false; replay.placements.len()];
+                    // Later placements are usually less important, trying them first keeps the result closer to the intended progression
+                    for &candidate in candidates.iter().rev() {
+                        excluded[candidate] = true;
+                        let playthrough = replay.play(&excluded);
+                        if !replay.goal_met(goal, world_index, &playthrough) {
+                            excluded[candidate] = false;
+                        }
+                    }
+
+                    let placements = candidates
+                        .iter()
+                        .filter(|&&index| !excluded[index])
+                        .map(|&index| replay.placements[index].clone())
+                        .collect();
+
+                    RequiredPlacements {
+                        goal: goal.cloned(),
+                        met: true,
+                        placements,
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        files::{self, FILE_SYSTEM_ACCESS, NO_FILE_ACCESS},
+        generate_seed, logic,
+        settings::{Spawn, UniverseSettings},
+    };
+
+    #[test]
+    fn required_placements_finish() {
+        let universe_settings = UniverseSettings {
+            required_items: true,
+            ..UniverseSettings::default()
+        };
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+
+        let seed = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).unwrap();
+        let required = &seed.spoiler.required[0];
+        assert!(required.iter().all(|required| required.met));
+        let finish = required
+            .iter()
+            .find(|required| required.goal.is_none())
+            .unwrap();
+        assert!(!finish.placements.is_empty());
+        assert!(finish
+            .placements
+            .iter()
+            .all(|placement| !matches!(placement.item, Item::SpiritLight(_))));
+        assert!(seed.spoiler.to_string().contains("Required to finish"));
+    }
+
+    #[test]
+    fn required_placements_spawn() {
+        let mut universe_settings = UniverseSettings {
+            required_items: true,
+            ..UniverseSettings::default()
+        };
+        universe_settings.world_settings[0].spawn = Spawn::Set("HowlsDen.Teleporter".to_string());
+        universe_settings.world_settings[0].headers =
+            FxHashSet::from_iter(["better_random_spawn".to_string()]);
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+
+        let seed = generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings).unwrap();
+        let required = &seed.spoiler.required[0];
+        assert!(required.iter().all(|required| required.met));
+        // Progression is placed on the spawn when spawning somewhere else than the default spawn
+        assert!(required
+            .iter()
+            .flat_map(|required| &required.placements)
+            .any(|placement| placement.location.identifier == "Spawn"));
+    }
+}
@@ -40,6 +40,20 @@ impl Seed<'_, '_> {
     ///
     /// May error if postprocessing commands (such as `$WHEREIS`) contain invalid regexes
     pub fn seed_files(&self) -> Result<Vec<String>, String> {
+        // The randomizer client has no command to change door connections yet, so the layout is only written as a comment for reference
+        let doors = if self.spoiler.doors.is_empty() {
+            String::new()
+        } else {
+            let doors = self
+                .spoiler
+                .doors
+                .iter()
+                .map(|door| format!("{}>{}", door.from, door.to))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("// Doors: {doors}\n")
+        };
+
         let mut seeds = self
             .worlds
             .iter()
@@ -52,6 +66,7 @@ impl Seed<'_, '_> {
                 format!(
                     "\
                         {world}\
+                        {doors}\
                         // This World: {index}\n\
                         // Target: ^2.0\n\
                         // Generator Version: {version}\n\
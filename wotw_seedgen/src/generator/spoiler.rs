@@ -2,8 +2,10 @@ use std::fmt::{self, Display, Write};
 
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
+use wotw_seedgen_derive::FromStr;
 
 use crate::{
+    settings::Goal,
     util::{Position, Zone},
     Inventory, Item,
 };
@@ -15,6 +17,16 @@ pub struct SeedSpoiler {
     pub spawns: Vec<String>,
     /// Each [`SpoilerGroup`] represents one "step" of placements
     pub groups: Vec<SpoilerGroup>,
+    /// The [`RequiredPlacements`] for each goal of each world
+    ///
+    /// This is only filled if [`UniverseSettings::required_items`](crate::settings::UniverseSettings::required_items) was set
+    #[serde(default)]
+    pub required: Vec<Vec<RequiredPlacements>>,
+    /// Where each door leads
+    ///
+    /// This is only filled if [`UniverseSettings::door_randomization`](crate::settings::UniverseSettings::door_randomization) was set
+    #[serde(default)]
+    pub doors: Vec<DoorConnection>,
 }
 /// One "step" of placements in a [`SeedSpoiler`]
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
@@ -44,6 +56,29 @@ pub struct SpoilerPlacement {
     /// The name of the [`Item`], which may vary from the [`Item`]s [`Display`] implementation if a custom name for item was provided by headers
     pub item_name: String,
 }
+/// A minimal set of placements needed to achieve a goal
+///
+/// Spirit Light placements are never considered, they are assumed to always be collected
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RequiredPlacements {
+    /// The [`Goal`] these placements are needed for, or `None` for reaching the final fight
+    pub goal: Option<Goal>,
+    /// Whether the goal was achieved when replaying the seed
+    ///
+    /// If this is `false`, `placements` will be empty
+    pub met: bool,
+    /// The needed placements, in the order they were placed
+    pub placements: Vec<SpoilerPlacement>,
+}
+/// A door and the anchor it leads to
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DoorConnection {
+    /// Identifier of the anchor the door is in
+    pub from: String,
+    /// Identifier of the anchor the door leads to
+    pub to: String,
+}
 /// Select data from a [`Node`](crate::world::graph::Node)
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct NodeSummary {
@@ -55,12 +90,235 @@ pub struct NodeSummary {
     pub zone: Option<Zone>,
 }
 
+/// The available ways to render a [`SeedSpoiler`]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    FromStr,
+    wotw_seedgen_derive::Display,
+)]
+#[ParseFromIdentifier]
+pub enum SpoilerFormat {
+    /// The human readable [`Display`] implementation
+    Text,
+    /// See [`SeedSpoiler::to_json`]
+    Json,
+    /// See [`SeedSpoiler::to_csv`]
+    Csv,
+    /// See [`SeedSpoiler::to_markdown`]
+    Markdown,
+    /// See [`SeedSpoiler::to_zone_summary`]
+    #[Ident = "zones"]
+    ZoneSummary,
+}
+impl SpoilerFormat {
+    /// The file extension commonly used for this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            SpoilerFormat::Text | SpoilerFormat::ZoneSummary => "txt",
+            SpoilerFormat::Json => "json",
+            SpoilerFormat::Csv => "csv",
+            SpoilerFormat::Markdown => "md",
+        }
+    }
+}
+
 impl SeedSpoiler {
     /// Serialize into json format
     pub fn to_json(&self) -> String {
         // This is safe because the SeedSpoiler struct is known to serialize successfully
         serde_json::to_string(&self).unwrap()
     }
+
+    /// Render in the given [`SpoilerFormat`]
+    pub fn render(&self, format: SpoilerFormat) -> String {
+        match format {
+            SpoilerFormat::Text => self.to_string(),
+            SpoilerFormat::Json => self.to_json(),
+            SpoilerFormat::Csv => self.to_csv(),
+            SpoilerFormat::Markdown => self.to_markdown(),
+            SpoilerFormat::ZoneSummary => self.to_zone_summary(),
+        }
+    }
+
+    /// Render as csv with one row per placement
+    ///
+    /// The columns are `step,origin_world,target_world,item,location,zone,x,y`, where `zone`, `x` and `y` may be empty
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("step,origin_world,target_world,item,location,zone,x,y\n");
+
+        for (step, placement) in self.placements() {
+            let zone = placement
+                .location
+                .zone
+                .map_or_else(String::new, |zone| zone.to_string());
+            let (x, y) = placement
+                .location
+                .position
+                .as_ref()
+                .map_or_else(Default::default, |position| {
+                    (position.x.to_string(), position.y.to_string())
+                });
+
+            // Writing to a String is infallible
+            writeln!(
+                csv,
+                "{step},{},{},{},{},{zone},{x},{y}",
+                placement.origin_world_index,
+                placement.target_world_index,
+                csv_field(&placement.item_name),
+                csv_field(&placement.location.identifier),
+            )
+            .unwrap();
+        }
+
+        csv
+    }
+
+    /// Render as markdown with one table of placements per step
+    pub fn to_markdown(&self) -> String {
+        let multiworld = self.spawns.len() > 1;
+        let mut markdown = String::from("# Spoiler\n\n");
+
+        // Writing to a String is infallible
+        for (index, spawn) in self.spawns.iter().enumerate() {
+            if multiworld {
+                writeln!(markdown, "- Spawn for World {index}: `{spawn}`").unwrap();
+            } else {
+                writeln!(markdown, "- Spawn: `{spawn}`").unwrap();
+            }
+        }
+
+        for (index, group) in self.groups.iter().enumerate() {
+            if group.placements.is_empty() {
+                continue;
+            }
+
+            write!(markdown, "\n## Step {index}").unwrap();
+            if group.reachable.is_empty() {
+                markdown.push_str(" (priority placements)");
+            }
+            markdown.push_str("\n\n");
+
+            if multiworld {
+                markdown.push_str("| Item | Target World | Location | Origin World | Zone |\n");
+                markdown.push_str("| --- | --- | --- | --- | --- |\n");
+            } else {
+                markdown.push_str("| Item | Location | Zone |\n");
+                markdown.push_str("| --- | --- | --- |\n");
+            }
+
+            for placement in &group.placements {
+                let item = placement.item_name.replace('|', "\\|");
+                let location = &placement.location.identifier;
+                let zone = placement
+                    .location
+                    .zone
+                    .map_or_else(String::new, |zone| zone.to_string());
+
+                if multiworld {
+                    writeln!(
+                        markdown,
+                        "| {item} | {} | {location} | {} | {zone} |",
+                        placement.target_world_index, placement.origin_world_index
+                    )
+                    .unwrap();
+                } else {
+                    writeln!(markdown, "| {item} | {location} | {zone} |").unwrap();
+                }
+            }
+        }
+
+        markdown
+    }
+
+    /// Render a compact summary of what each world's zones contain
+    pub fn to_zone_summary(&self) -> String {
+        let multiworld = self.spawns.len() > 1;
+        let mut summary = String::new();
+
+        for world_index in 0..self.spawns.len() {
+            if multiworld {
+                // Writing to a String is infallible
+                writeln!(summary, "World [{world_index}]").unwrap();
+            }
+
+            let placements = self
+                .placements()
+                .map(|(_, placement)| placement)
+                .filter(|placement| placement.origin_world_index == world_index)
+                .collect::<Vec<_>>();
+
+            // Placements without a zone, such as items granted on spawn, are listed last
+            let zones = placements
+                .iter()
+                .map(|placement| placement.location.zone)
+                .unique()
+                .sorted_by_key(|zone| (zone.is_none(), *zone));
+            for zone in zones {
+                let zone_placements = placements
+                    .iter()
+                    .filter(|placement| placement.location.zone == zone)
+                    .collect::<Vec<_>>();
+
+                let count = |matches: fn(&Item) -> bool| {
+                    zone_placements
+                        .iter()
+                        .filter(|placement| matches(&placement.item))
+                        .count()
+                };
+                let skills = count(|item| matches!(item, Item::Skill(_)));
+                let teleporters = count(|item| matches!(item, Item::Teleporter(_)));
+                let shards = count(|item| matches!(item, Item::Shard(_)));
+                let spirit_light: u32 = zone_placements
+                    .iter()
+                    .filter_map(|placement| match placement.item {
+                        Item::SpiritLight(amount) => Some(amount),
+                        _ => None,
+                    })
+                    .sum();
+
+                let zone = zone.map_or_else(|| "Other".to_string(), |zone| zone.to_string());
+                writeln!(
+                    summary,
+                    "{zone}: {} pickups, {skills} Skills, {teleporters} Teleporters, {shards} Shards, {spirit_light} Spirit Light",
+                    zone_placements.len()
+                )
+                .unwrap();
+            }
+
+            if multiworld {
+                summary.push('\n');
+            }
+        }
+
+        summary
+    }
+
+    /// All placements with the index of their [`SpoilerGroup`]
+    fn placements(&self) -> impl Iterator<Item = (usize, &SpoilerPlacement)> {
+        self.groups.iter().enumerate().flat_map(|(index, group)| {
+            group
+                .placements
+                .iter()
+                .map(move |placement| (index, placement))
+        })
+    }
+}
+
+/// Quotes a csv field if necessary
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
 }
 
 impl Display for SeedSpoiler {
@@ -172,6 +430,127 @@ impl Display for SeedSpoiler {
             writeln!(f)?;
         }
 
+        if !self.doors.is_empty() {
+            writeln!(f, "Doors")?;
+            for door in &self.doors {
+                writeln!(f, "    {} -> {}", door.from, door.to)?;
+            }
+            writeln!(f)?;
+        }
+
+        for (world_index, world_required) in self.required.iter().enumerate() {
+            for required in world_required {
+                if multiworld {
+                    write!(f, "[{world_index}] ")?;
+                }
+                match &required.goal {
+                    Some(goal) => writeln!(f, "Required for {goal}")?,
+                    None => writeln!(f, "Required to finish")?,
+                }
+                if !required.met {
+                    writeln!(f, "    Not achieved when replaying the seed")?;
+                }
+
+                for placement in &required.placements {
+                    write!(f, "    ")?;
+                    if multiworld {
+                        write!(f, "[{}] ", placement.target_world_index)?;
+                    }
+                    write!(f, "{}  ", placement.item_name)?;
+                    if multiworld {
+                        write!(f, "[{}] ", placement.origin_world_index)?;
+                    }
+                    writeln!(f, "{}", placement.location.identifier)?;
+                }
+
+                writeln!(f)?;
+            }
+        }
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::Skill;
+
+    fn placement(item: Item, item_name: &str, identifier: &str, zone: Zone) -> SpoilerPlacement {
+        SpoilerPlacement {
+            origin_world_index: 0,
+            target_world_index: 0,
+            location: NodeSummary {
+                identifier: identifier.to_string(),
+                position: None,
+                zone: Some(zone),
+            },
+            item,
+            item_name: item_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn spoiler_formats() {
+        let spoiler = SeedSpoiler {
+            spawns: vec!["MarshSpawn.Main".to_string()],
+            groups: vec![SpoilerGroup {
+                reachable: vec![vec![]],
+                forced_items: Inventory::default(),
+                placements: vec![
+                    placement(
+                        Item::Skill(Skill::Launch),
+                        "Launch",
+                        "MarshSpawn.RockHC",
+                        Zone::Marsh,
+                    ),
+                    placement(
+                        Item::SpiritLight(100),
+                        "100 Spirit Light, probably",
+                        "MarshSpawn.CaveEX",
+                        Zone::Marsh,
+                    ),
+                    placement(
+                        Item::Skill(Skill::Grapple),
+                        "Grapple",
+                        "OpherShop.WaterBreath",
+                        Zone::Shop,
+                    ),
+                    SpoilerPlacement {
+                        location: NodeSummary {
+                            identifier: "Spawn".to_string(),
+                            position: None,
+                            zone: None,
+                        },
+                        ..placement(Item::SpiritLight(50), "50 Spirit Light", "", Zone::Void)
+                    },
+                ],
+            }],
+            required: Vec::new(),
+            doors: Vec::new(),
+        };
+
+        for format in ["text", "json", "csv", "markdown", "zones"] {
+            let format: SpoilerFormat = format.parse().unwrap();
+            assert!(!spoiler.render(format).is_empty());
+        }
+
+        let csv = spoiler.to_csv();
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.contains("\"100 Spirit Light, probably\""));
+
+        let markdown = spoiler.to_markdown();
+        assert!(markdown.contains("## Step 0"));
+        assert!(markdown.contains("| Launch | MarshSpawn.RockHC | Marsh |"));
+
+        let summary = spoiler.to_zone_summary();
+        assert_eq!(
+            summary,
+            "\
+Marsh: 2 pickups, 1 Skills, 0 Teleporters, 0 Shards, 100 Spirit Light
+Shop: 1 pickups, 1 Skills, 0 Teleporters, 0 Shards, 0 Spirit Light
+Other: 1 pickups, 0 Skills, 0 Teleporters, 0 Shards, 50 Spirit Light
+"
+        );
+    }
+}
@@ -0,0 +1,46 @@
+//! Types shared by the analysis tools of the individual languages, as used by language servers
+//!
+//! All offsets are byte offsets into the source
+
+use std::ops::Range;
+
+/// Information to show when hovering over part of the source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hover {
+    /// The part of the source this information is about
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    pub label: String,
+    pub kind: CompletionKind,
+    /// Short information displayed next to the label
+    pub detail: Option<String>,
+    pub documentation: Option<String>,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    /// A header command following `!!`
+    Command,
+    /// A configuration parameter declared with `!!parameter`
+    Parameter,
+    /// A skill usable in logic requirements
+    Skill,
+    /// A trick usable in logic requirements
+    Trick,
+    /// A named requirement declared in the logic
+    Requirement,
+    /// An anchor declared in the logic
+    Anchor,
+}
+
+/// Returns the start and content of the line containing `offset`
+pub(crate) fn line_at(input: &str, offset: usize) -> (usize, &str) {
+    let start = input[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let end = input[offset..]
+        .find('\n')
+        .map_or(input.len(), |index| offset + index);
+    (start, input[start..end].trim_end_matches('\r'))
+}
@@ -0,0 +1,367 @@
+//! Tools to inspect header syntax while it is being written, as used by language servers
+//!
+//! All offsets are byte offsets into the header source
+
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::{
+    languages::{
+        analysis::{line_at, Completion, CompletionKind, Hover},
+        ParseError,
+    },
+    uber_state::UberIdentifier,
+    util::extensions::StrExtension,
+    Item,
+};
+
+use super::{
+    parser::{self, parse_header_contents},
+    Header,
+};
+
+/// Header commands available after `!!`, along with a short description
+const COMMANDS: &[(&str, &str)] = &[
+    ("include", "Include another header"),
+    ("exclude", "Mark another header as incompatible"),
+    ("add", "Add an item to the item pool"),
+    ("remove", "Remove an item from the item pool"),
+    ("name", "Set the name of an item"),
+    ("display", "Set the name an item shows when picked up"),
+    ("description", "Set the shop description of an item"),
+    ("price", "Set the shop price of an item"),
+    ("icon", "Set the shop icon of an item"),
+    ("parameter", "Declare a configuration parameter"),
+    ("set", "Set a logical state at the start of seed generation"),
+    ("if", "Only use the following lines if a parameter matches"),
+    ("endif", "End the last !!if"),
+    ("goal", "Add a goal required to finish the game"),
+    ("pool", "Add a line to the pool used by !!take"),
+    ("flush", "Empty the pool used by !!take"),
+    ("take", "Replace with a random line from the pool"),
+];
+
+/// The target of an `!!include` command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Include<'a> {
+    /// Position of the header name
+    pub range: Range<usize>,
+    /// Name of the included header, without file extension
+    pub name: &'a str,
+}
+
+/// Returns all errors in the header syntax
+///
+/// Lines using `!!pool`, `!!flush` or `!!take` are skipped since their contents depend on the rng used when parsing
+pub fn diagnostics(input: &str) -> Vec<ParseError> {
+    let mut checked = input.to_string();
+    for range in input.line_ranges() {
+        let line = &input[range.clone()];
+        if line.starts_with("!!pool") || line.starts_with("!!flush") || line.contains("!!take") {
+            // Replace with whitespace of the same length to keep the other errors in place
+            let content = line.trim_end_matches(&['\r', '\n'][..]);
+            checked.replace_range(
+                range.start..range.start + content.len(),
+                &" ".repeat(content.len()),
+            );
+        }
+    }
+
+    let mut parser = parser::new(&checked);
+    match parse_header_contents(&mut parser) {
+        Ok(_) => vec![],
+        Err(errors) => errors
+            .iter()
+            .cloned()
+            .map(|mut error| {
+                error.source = input.to_string();
+                error
+            })
+            .collect(),
+    }
+}
+
+/// Returns information about the header command, uberState or item at `offset`
+pub fn hover(input: &str, offset: usize) -> Option<Hover> {
+    let (start, line) = line_at(input, offset);
+    let line = trim_comment(line);
+
+    if let Some(command) = line.strip_prefix("!!") {
+        command_hover(command, start + 2, offset)
+    } else if let Some(timer) = line.strip_prefix("timer:") {
+        let segments = segments(timer, start + 6);
+        segments
+            .chunks(2)
+            .find_map(|identifier| uber_identifier_hover(identifier, offset))
+    } else {
+        let (pickup, start) = match line.strip_prefix('!') {
+            Some(pickup) => (pickup, start + 1),
+            None => (line, start),
+        };
+        pickup_hover(pickup, start, offset)
+    }
+}
+
+/// Returns the possible completions at `offset`
+///
+/// Header commands are completed after `!!` and parameter names inside `$PARAM(...)` and after `!!if`
+pub fn completions(input: &str, offset: usize) -> Vec<Completion> {
+    let (start, line) = line_at(input, offset);
+    let before = &line[..(offset - start).min(line.len())];
+
+    if let Some(partial) = before.strip_prefix("!!") {
+        if partial.chars().all(is_ident_char) {
+            return COMMANDS
+                .iter()
+                .map(|(command, description)| Completion {
+                    label: command.to_string(),
+                    kind: CompletionKind::Command,
+                    detail: Some(description.to_string()),
+                    documentation: None,
+                })
+                .collect();
+        }
+    }
+
+    let parameter = match before.to_ascii_lowercase().rfind("$param(") {
+        Some(index) => before[index + 7..].chars().all(is_ident_char),
+        None => match before.strip_prefix("!!if ") {
+            Some(partial) => partial.trim_start().chars().all(is_ident_char),
+            None => false,
+        },
+    };
+    if parameter {
+        return Header::parse_parameters(input)
+            .into_iter()
+            .map(|parameter| Completion {
+                label: parameter.identifier,
+                kind: CompletionKind::Parameter,
+                detail: Some(format!("default: {}", parameter.default)),
+                documentation: parameter.documentation,
+            })
+            .collect();
+    }
+
+    vec![]
+}
+
+/// Returns the `!!include` command on the line at `offset`, if there is one
+pub fn include_at<'a>(input: &'a str, offset: usize) -> Option<Include<'a>> {
+    let (start, line) = line_at(input, offset);
+    let arguments = trim_comment(line).strip_prefix("!!include")?;
+    let name = arguments.trim_start();
+    if name.is_empty() || name.len() == arguments.len() {
+        return None;
+    }
+
+    let name_start = start + "!!include".len() + arguments.len() - name.len();
+    Some(Include {
+        range: name_start..name_start + name.len(),
+        name,
+    })
+}
+
+fn trim_comment(line: &str) -> &str {
+    line.find("//")
+        .map_or(line, |index| &line[..index])
+        .trim_end()
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Splits `text` on `|` and returns each part with its range
+fn segments(text: &str, start: usize) -> Vec<(Range<usize>, &str)> {
+    let mut segment_start = start;
+    text.split('|')
+        .map(|segment| {
+            let range = segment_start..segment_start + segment.len();
+            segment_start = range.end + 1;
+            (range, segment)
+        })
+        .collect()
+}
+
+fn command_hover(command: &str, start: usize, offset: usize) -> Option<Hover> {
+    let name_end = command.find(char::is_whitespace).unwrap_or(command.len());
+    let name = &command[..name_end];
+    if offset <= start + name_end {
+        return COMMANDS
+            .iter()
+            .find(|(command, _)| command.eq_ignore_ascii_case(name))
+            .map(|(_, description)| Hover {
+                range: start..start + name_end,
+                text: description.to_string(),
+            });
+    }
+
+    let arguments = &command[name_end..];
+    let mut item = arguments.trim_start();
+    if matches!(&name.to_lowercase()[..], "add" | "remove") {
+        // An amount like "3x" or "$PARAM(amount)x" may precede the item
+        if let Some(amount_end) = item.find(char::is_whitespace) {
+            let amount = &item[..amount_end];
+            if amount.len() > 1
+                && amount.ends_with('x')
+                && (amount.starts_with('$')
+                    || amount[..amount.len() - 1]
+                        .chars()
+                        .all(|c| c.is_ascii_digit()))
+            {
+                item = item[amount_end..].trim_start();
+            }
+        }
+    } else if !matches!(
+        &name.to_lowercase()[..],
+        "name" | "display" | "description" | "price" | "icon"
+    ) {
+        return None;
+    }
+    let item_start = start + command.len() - item.len();
+    let item = &item[..item.find(char::is_whitespace).unwrap_or(item.len())];
+
+    item_hover(item, item_start, offset)
+}
+
+fn pickup_hover(pickup: &str, start: usize, offset: usize) -> Option<Hover> {
+    let segments = segments(pickup, start);
+    if segments.len() < 3 {
+        return None;
+    }
+    if let Some(hover) = uber_identifier_hover(&segments[..2], offset) {
+        return Some(hover);
+    }
+
+    let item_start = segments[2].0.start;
+    let last = segments.last().unwrap();
+    let item_end = if segments.len() > 3 && last.1.trim().eq_ignore_ascii_case("mute") {
+        last.0.start - 1
+    } else {
+        last.0.end
+    };
+    item_hover(
+        &pickup[item_start - start..item_end - start],
+        item_start,
+        offset,
+    )
+}
+
+fn item_hover(item: &str, start: usize, offset: usize) -> Option<Hover> {
+    let range = start..start + item.len();
+    if !(range.start..=range.end).contains(&offset) {
+        return None;
+    }
+
+    let segments = segments(item, start);
+    if segments[0].1 == "8" && segments.len() > 2 {
+        if let Some(hover) = uber_identifier_hover(&segments[1..3], offset) {
+            return Some(hover);
+        }
+    }
+
+    Item::from_str(item.trim()).ok().map(|item| Hover {
+        range,
+        text: item.to_string(),
+    })
+}
+
+/// Hover for an uberState identifier split into the group and id segments
+///
+/// The id segment may be followed by a condition like `=5`
+fn uber_identifier_hover(segments: &[(Range<usize>, &str)], offset: usize) -> Option<Hover> {
+    let (group_range, group) = segments.first()?;
+    let (id_range, id) = segments.get(1)?;
+    let id_len = id.find(&['=', '>', '<'][..]).unwrap_or(id.len());
+    let range = group_range.start..id_range.start + id_len;
+    if !(range.start..=range.end).contains(&offset) {
+        return None;
+    }
+
+    let identifier = UberIdentifier::new(
+        group.trim().parse().ok()?,
+        id[..id_len].trim().parse().ok()?,
+    );
+    let text = match identifier.name() {
+        Some(name) => format!("{name} ({})", identifier.code()),
+        None => format!("Unknown uberState {}", identifier.code()),
+    };
+    Some(Hover { range, text })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "\
+/// Test header
+!!include bonus_items
+//// Extra spirit light
+!!parameter extra int:100
+3|0|8|9|0|int|5 // comment
+!!pool 2|100
+!!add 3x 0|$PARAM(extra)
+!!name 0|200 Big SL
+timer: 6|2|6|3
+";
+
+    fn offset_of(pattern: &str) -> usize {
+        HEADER.find(pattern).unwrap()
+    }
+
+    #[test]
+    fn header_diagnostics() {
+        let errors = diagnostics(HEADER);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let input = "3|0|2|100\n!!pool 2|1\n2|100|2|!!take\n3|0|x\n";
+        let errors = diagnostics(input);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].range.start, input.find('x').unwrap());
+    }
+
+    #[test]
+    fn header_hover() {
+        let text = |offset| hover(HEADER, offset).map(|hover| hover.text);
+        assert_eq!(
+            text(offset_of("3|0|8")),
+            Some("game_state.Spawn (3|0)".to_string())
+        );
+        assert_eq!(
+            text(offset_of("9|0|int")),
+            Some("plando_vars.0_int (9|0)".to_string())
+        );
+        assert!(text(offset_of("|int|5") + 1).is_some());
+        assert_eq!(text(offset_of("include")), Some(COMMANDS[0].1.to_string()));
+        assert_eq!(
+            text(offset_of("0|200")),
+            Some("200 Spirit Light".to_string())
+        );
+        assert_eq!(
+            text(offset_of("6|2")),
+            Some("rando_state.Pickups Collected (6|2)".to_string())
+        );
+        assert_eq!(text(offset_of("comment")), None);
+    }
+
+    #[test]
+    fn header_completions() {
+        let labels = |offset| {
+            completions(HEADER, offset)
+                .into_iter()
+                .map(|completion| completion.label)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(offset_of("include")).len(), COMMANDS.len());
+        assert_eq!(labels(offset_of("extra)")), ["extra"]);
+        assert_eq!(labels(offset_of("3|0|8")), Vec::<String>::new());
+    }
+
+    #[test]
+    fn header_include() {
+        let include = include_at(HEADER, offset_of("!!include")).unwrap();
+        assert_eq!(include.name, "bonus_items");
+        assert_eq!(&HEADER[include.range], "bonus_items");
+        assert_eq!(include_at(HEADER, offset_of("!!parameter")), None);
+    }
+}
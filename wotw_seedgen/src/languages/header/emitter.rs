@@ -2,9 +2,17 @@ use std::fmt::Display;
 
 use rustc_hash::FxHashMap;
 
-use crate::{settings::Goal, util::Icon, Item, VItem};
+use crate::{
+    settings::{CustomGoal, Goal},
+    uber_state::VUberStateTrigger,
+    util::Icon,
+    Item, VItem,
+};
 
-use super::{GoalmodeHack, HeaderCommand, HeaderContent, Pickup, VPickup, VResolve, VString, V};
+use super::{
+    split_list, GoalmodeHack, HeaderCommand, HeaderContent, ParameterCondition, Pickup, VPickup,
+    VResolve, VString, V,
+};
 
 /// Configurable details for how to treat an [`Item`] during seed generation
 #[derive(Debug, Clone, Default)]
@@ -144,13 +152,17 @@ fn build_command(
         }
         HeaderCommand::Parameter { .. } => { /* Skip, parameters have been processed earlier */ }
         HeaderCommand::Set { state } => header_build.state_sets.push(state),
-        HeaderCommand::If { parameter, value } => {
-            build_if(&parameter, &value, if_stack, parameters)?
-        }
+        HeaderCommand::If {
+            parameter,
+            condition,
+        } => build_if(&parameter, &condition, if_stack, parameters)?,
         HeaderCommand::EndIf => build_endif(if_stack)?,
         HeaderCommand::GoalmodeHack(goalmode) => {
             build_goalmode(goalmode, &mut header_build.goals, parameters)?
         }
+        HeaderCommand::Goal { conditions, flag } => {
+            build_goal(conditions, flag, &mut header_build.goals, parameters)?
+        }
     }
 
     Ok(())
@@ -158,14 +170,26 @@ fn build_command(
 
 fn build_if(
     parameter: &str,
-    value: &str,
+    condition: &ParameterCondition,
     if_stack: &mut Vec<bool>,
     parameters: &FxHashMap<String, String>,
 ) -> Result<(), String> {
-    let met = parameters
+    let value = parameters
         .get(parameter)
-        .ok_or_else(|| format!("Unknown parameter {parameter} in if"))?
-        == value;
+        .ok_or_else(|| format!("Unknown parameter {parameter} in if"))?;
+    let number = || {
+        value
+            .parse::<f64>()
+            .map_err(|_| format!("Cannot compare non-numeric parameter {parameter} in if"))
+    };
+    let met = match condition {
+        ParameterCondition::Equals(expected) => value == expected,
+        ParameterCondition::Greater(than) => number()? > *than,
+        ParameterCondition::Less(than) => number()? < *than,
+        ParameterCondition::In(values) => {
+            split_list(value).any(|value| values.iter().any(|listed| listed == value))
+        }
+    };
     if_stack.push(met);
     Ok(())
 }
@@ -286,3 +310,19 @@ fn build_goalmode(
 
     Ok(())
 }
+fn build_goal(
+    conditions: Vec<VUberStateTrigger>,
+    flag: VString,
+    goals: &mut Vec<Goal>,
+    parameters: &FxHashMap<String, String>,
+) -> Result<(), String> {
+    let conditions = conditions
+        .into_iter()
+        .map(|condition| condition.resolve(parameters))
+        .collect::<Result<_, _>>()?;
+    let flag = flag.resolve(parameters)?;
+
+    goals.push(Goal::Custom(CustomGoal { flag, conditions }));
+
+    Ok(())
+}
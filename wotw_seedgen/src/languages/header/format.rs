@@ -0,0 +1,255 @@
+use crate::languages::{parser::ParseErrorCollection, TokenKind};
+
+use super::{
+    analysis,
+    parser::{self, parse_header_contents},
+    tokenizer::tokenize,
+    Annotation, HeaderContent,
+};
+
+/// Formats header syntax into the canonical style
+///
+/// - Timers, flags and annotations are rewritten from their parsed representation
+/// - Whitespace around `|` is removed, other tokens are kept in their original order
+/// - Trailing comments on consecutive lines are aligned
+/// - Runs of blank lines are collapsed and `!!if` blocks are separated by exactly one blank line
+///
+/// Comments and documentation are kept as written. Returns an error if the header syntax is invalid
+pub fn format_header(input: &str) -> Result<String, ParseErrorCollection> {
+    let errors = analysis::diagnostics(input);
+    if !errors.is_empty() {
+        return Err(errors.into());
+    }
+
+    let mut lines = Vec::<Line>::new();
+    let mut after_endif = false;
+    for line in input.lines().map(Line::parse) {
+        if line == Line::Blank {
+            if lines
+                .last()
+                .is_some_and(|last| *last != Line::Blank && !last.is_if())
+            {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        if line.is_endif() {
+            while lines.last() == Some(&Line::Blank) {
+                lines.pop();
+            }
+        } else if after_endif || line.is_if() {
+            // Comments right above the block belong to it
+            let block_start = lines.len()
+                - lines
+                    .iter()
+                    .rev()
+                    .take_while(|line| matches!(line, Line::Comment(_)))
+                    .count();
+            let nested = line.is_if()
+                && block_start > 0
+                && lines[block_start - 1].is_if()
+                && block_start == lines.len();
+            if block_start > 0 && lines[block_start - 1] != Line::Blank && !nested {
+                lines.insert(block_start, Line::Blank);
+            }
+        }
+        after_endif = line.is_endif();
+
+        lines.push(line);
+    }
+    while lines.last() == Some(&Line::Blank) {
+        lines.pop();
+    }
+
+    let mut output = String::new();
+    for block in lines.split(|line| *line == Line::Blank) {
+        let comment_column = block
+            .iter()
+            .filter_map(|line| match line {
+                Line::Code {
+                    code,
+                    comment: Some(_),
+                } => Some(code.chars().count()),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default();
+
+        for line in block {
+            match line {
+                Line::Blank => {}
+                Line::Comment(comment) => output.push_str(comment),
+                Line::Code { code, comment } => {
+                    output.push_str(code);
+                    if let Some(comment) = comment {
+                        let padding = comment_column - code.chars().count() + 2;
+                        output.push_str(&" ".repeat(padding));
+                        output.push_str(comment);
+                    }
+                }
+            }
+            output.push('\n');
+        }
+        output.push('\n');
+    }
+    output.pop();
+
+    Ok(output)
+}
+
+#[derive(PartialEq)]
+enum Line<'a> {
+    Blank,
+    /// A line containing only a comment or documentation
+    Comment(&'a str),
+    Code {
+        code: String,
+        comment: Option<&'a str>,
+    },
+}
+impl<'a> Line<'a> {
+    fn parse(line: &'a str) -> Line<'a> {
+        let line = line.trim();
+        if line.is_empty() {
+            Line::Blank
+        } else if line.starts_with("//") {
+            Line::Comment(line)
+        } else {
+            let (code, comment) = match line.find("//") {
+                Some(index) => (line[..index].trim_end(), Some(&line[index..])),
+                None => (line, None),
+            };
+            Line::Code {
+                code: format_code(code),
+                comment,
+            }
+        }
+    }
+
+    fn command(&self) -> Option<&str> {
+        match self {
+            Line::Code { code, .. } => code.strip_prefix("!!"),
+            _ => None,
+        }
+    }
+    fn is_if(&self) -> bool {
+        self.command().is_some_and(|command| {
+            command.len() > 2
+                && command[..2].eq_ignore_ascii_case("if")
+                && command[2..].starts_with(char::is_whitespace)
+        })
+    }
+    fn is_endif(&self) -> bool {
+        self.command()
+            .is_some_and(|command| command.eq_ignore_ascii_case("endif"))
+    }
+}
+
+/// Rewrites timers, flags and annotations from their parsed representation
+///
+/// Pickups only have their separators formatted, rewriting them would reorder message flags
+fn format_code(code: &str) -> String {
+    if code.starts_with("!!") {
+        return format_separators(code);
+    }
+
+    let mut parser = parser::new(code);
+    let content = match parse_header_contents(&mut parser).as_deref() {
+        Ok([content]) => content.clone(),
+        _ => return format_separators(code),
+    };
+    match content {
+        HeaderContent::Timer(timer) => format!("timer: {}", timer.code()),
+        HeaderContent::Flags(flags) => format!("Flags: {}", flags.join(", ")),
+        HeaderContent::Annotation(Annotation::Hide) => "#hide".to_string(),
+        HeaderContent::Annotation(Annotation::Category(category)) => {
+            format!("#category {category}")
+        }
+        _ => format_separators(code),
+    }
+}
+
+/// Removes whitespace around `|`
+fn format_separators(code: &str) -> String {
+    let tokens = tokenize(code).collect::<Vec<_>>();
+    let is_separator = |index: Option<usize>| {
+        index
+            .and_then(|index| tokens.get(index))
+            .map(|token| token.kind)
+            == Some(TokenKind::Separator)
+    };
+
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(index, token)| {
+            token.kind != TokenKind::Whitespace
+                || !(is_separator(index.checked_sub(1)) || is_separator(Some(index + 1)))
+        })
+        .map(|(_, token)| &code[token.range.clone()])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_formatting() {
+        let input = "\
+#category   Item Pool
+/// Test header
+////  Whether to add things
+!!parameter things bool:true
+
+
+3|0|2|100 // Bash
+!3|0|8|9|0|int|5    // comment
+!!add $PARAM(amount)x 0|200
+!!pool 2 | 100  // pooled
+2|100|2|!!take
+1|105|6|Black Market Keystone | mute|f=0
+// skip-validate
+!!if things true
+
+!!add 2|8 // Launch
+
+!!endif
+!!if things false
+!!add 0|50
+!!endif
+timer:6|2|6|3
+
+";
+        let expected = "\
+#category Item Pool
+/// Test header
+////  Whether to add things
+!!parameter things bool:true
+
+3|0|2|100         // Bash
+!3|0|8|9|0|int|5  // comment
+!!add $PARAM(amount)x 0|200
+!!pool 2|100      // pooled
+2|100|2|!!take
+1|105|6|Black Market Keystone|mute|f=0
+
+// skip-validate
+!!if things true
+!!add 2|8  // Launch
+!!endif
+
+!!if things false
+!!add 0|50
+!!endif
+
+timer: 6|2|6|3
+";
+        let formatted = format_header(input).unwrap();
+        assert_eq!(formatted, expected);
+        assert_eq!(format_header(&formatted).unwrap(), expected);
+
+        assert!(format_header("3|0|x\n").is_err());
+    }
+}
@@ -1,14 +1,19 @@
+pub mod analysis;
 pub mod tokenizer;
 use tokenizer::TokenStream;
 mod code;
 mod emitter;
+mod format;
 pub(crate) mod parser;
+mod testing;
 mod tools;
 mod v;
 
 pub use code::CodeDisplay;
 pub use emitter::{HeaderBuild, ItemDetails};
+pub use format::format_header;
 use std::{fmt, str::FromStr};
+pub use testing::{Expectation, HeaderTest, TestCase, TestResult};
 pub use tools::validate_headers;
 pub(crate) use v::vdisplay;
 pub use v::{VResolve, VString, V};
@@ -107,24 +112,9 @@ impl Header {
         } in own_parameters
         {
             if let Some(custom) = parameters.get(&identifier) {
-                match default.kind() {
-                    ParameterType::Bool => {
-                        custom
-                            .parse::<bool>()
-                            .map_err(|_| format!("invalid value for parameter {identifier}"))?;
-                    }
-                    ParameterType::Int => {
-                        custom
-                            .parse::<i32>()
-                            .map_err(|_| format!("invalid value for parameter {identifier}"))?;
-                    }
-                    ParameterType::Float => {
-                        custom
-                            .parse::<f32>()
-                            .map_err(|_| format!("invalid value for parameter {identifier}"))?;
-                    }
-                    ParameterType::String => {}
-                }
+                default
+                    .validate(custom)
+                    .map_err(|err| format!("invalid value for parameter {identifier}: {err}"))?;
             } else {
                 parameters.insert(identifier, default.to_string());
             }
@@ -515,10 +505,14 @@ pub enum HeaderCommand {
     },
     If {
         parameter: String,
-        value: String,
+        condition: ParameterCondition,
     },
     EndIf,
     GoalmodeHack(GoalmodeHack),
+    Goal {
+        conditions: Vec<VUberStateTrigger>,
+        flag: VString,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -529,6 +523,21 @@ pub enum GoalmodeHack {
     Relics { chance: V<f64>, amount: V<usize> },
 }
 
+/// Condition on a parameter's value used by `!!if`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterCondition {
+    /// `!!if parameter value`
+    Equals(String),
+    /// `!!if parameter > value`
+    Greater(f64),
+    /// `!!if parameter < value`
+    Less(f64),
+    /// `!!if parameter in value,value`
+    ///
+    /// For list parameters, this is met if any of their values is listed
+    In(Vec<String>),
+}
+
 /// Type and value of a parameter's default
 #[derive(Debug, Clone, PartialEq, FromStr)]
 #[ParseFromIdentifier]
@@ -537,6 +546,8 @@ pub enum ParameterType {
     Int,
     Float,
     String,
+    Enum,
+    List,
 }
 
 /// Type and value of a parameter's default
@@ -544,21 +555,112 @@ pub enum ParameterType {
 pub enum ParameterDefault {
     Bool(bool),
     Int(i32),
+    /// Integer limited to `min..=max`
+    IntRange {
+        min: i32,
+        max: i32,
+        default: i32,
+    },
     Float(f32),
     String(String),
+    /// One of a set of allowed values
+    Enum {
+        variants: Vec<String>,
+        default: String,
+    },
+    /// Comma-separated list of strings, optionally limited to a set of allowed values
+    List {
+        variants: Option<Vec<String>>,
+        default: Vec<String>,
+    },
 }
 
 impl ParameterDefault {
     pub fn kind(&self) -> ParameterType {
         match self {
             ParameterDefault::Bool(_) => ParameterType::Bool,
-            ParameterDefault::Int(_) => ParameterType::Int,
+            ParameterDefault::Int(_) | ParameterDefault::IntRange { .. } => ParameterType::Int,
             ParameterDefault::Float(_) => ParameterType::Float,
             ParameterDefault::String(_) => ParameterType::String,
+            ParameterDefault::Enum { .. } => ParameterType::Enum,
+            ParameterDefault::List { .. } => ParameterType::List,
+        }
+    }
+
+    /// Describes the values a parameter of this type accepts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wotw_seedgen::header::ParameterDefault;
+    ///
+    /// let default: ParameterDefault = "int(0,10):3".parse().unwrap();
+    ///
+    /// assert_eq!(default.type_description(), "integer from 0 to 10");
+    /// ```
+    pub fn type_description(&self) -> String {
+        match self {
+            ParameterDefault::Bool(_) => "boolean".to_string(),
+            ParameterDefault::Int(_) => "integer".to_string(),
+            ParameterDefault::IntRange { min, max, .. } => format!("integer from {min} to {max}"),
+            ParameterDefault::Float(_) => "number".to_string(),
+            ParameterDefault::String(_) => "string".to_string(),
+            ParameterDefault::Enum { variants, .. } => format!("one of {}", variants.join(", ")),
+            ParameterDefault::List { variants: None, .. } => "comma-separated list".to_string(),
+            ParameterDefault::List {
+                variants: Some(variants),
+                ..
+            } => format!("comma-separated list of {}", variants.join(", ")),
+        }
+    }
+
+    /// Checks whether `value` is allowed for a parameter of this type
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wotw_seedgen::header::ParameterDefault;
+    ///
+    /// let default: ParameterDefault = "enum(easy,hard):easy".parse().unwrap();
+    ///
+    /// assert!(default.validate("hard").is_ok());
+    /// assert!(default.validate("medium").is_err());
+    /// ```
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        let valid = match self {
+            ParameterDefault::Bool(_) => value.parse::<bool>().is_ok(),
+            ParameterDefault::Int(_) => value.parse::<i32>().is_ok(),
+            ParameterDefault::IntRange { min, max, .. } => value
+                .parse::<i32>()
+                .map_or(false, |value| (*min..=*max).contains(&value)),
+            ParameterDefault::Float(_) => value.parse::<f32>().is_ok(),
+            ParameterDefault::String(_) => true,
+            ParameterDefault::Enum { variants, .. } => {
+                variants.iter().any(|variant| variant == value)
+            }
+            ParameterDefault::List { variants: None, .. } => true,
+            ParameterDefault::List {
+                variants: Some(variants),
+                ..
+            } => split_list(value).all(|value| variants.iter().any(|variant| variant == value)),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("expected {}", self.type_description()))
         }
     }
 }
 
+/// Splits a list parameter's value into its elements
+pub(crate) fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
 impl FromStr for ParameterDefault {
     type Err = String;
 
@@ -570,38 +672,105 @@ impl FromStr for ParameterDefault {
         } else {
             ("string", first_part)
         };
+        let (parameter_type, variants) = match parameter_type.split_once('(') {
+            Some((parameter_type, variants)) => {
+                let variants = variants
+                    .strip_suffix(')')
+                    .ok_or_else(|| format!("missing ) in parameter type {parameter_type}"))?;
+                (
+                    parameter_type,
+                    Some(split_list(variants).map(str::to_string).collect::<Vec<_>>()),
+                )
+            }
+            None => (parameter_type, None),
+        };
 
-        let default = match parameter_type {
-            "bool" => ParameterDefault::Bool(
+        let default = match (parameter_type, variants) {
+            ("bool", None) => ParameterDefault::Bool(
                 default
                     .parse()
                     .map_err(|_| format!("invalid value boolean {default}"))?,
             ),
-            "int" => ParameterDefault::Int(
+            ("int", None) => ParameterDefault::Int(
                 default
                     .parse()
                     .map_err(|_| format!("invalid value integer {default}"))?,
             ),
-            "float" => ParameterDefault::Float(
+            ("int", Some(bounds)) => {
+                let (min, max) = match &bounds[..] {
+                    [min, max] => (
+                        min.parse()
+                            .map_err(|_| format!("invalid minimum integer {min}"))?,
+                        max.parse()
+                            .map_err(|_| format!("invalid maximum integer {max}"))?,
+                    ),
+                    _ => return Err("expected int(min,max)".to_string()),
+                };
+                ParameterDefault::int_range(
+                    min,
+                    max,
+                    default
+                        .parse()
+                        .map_err(|_| format!("invalid value integer {default}"))?,
+                )?
+            }
+            ("float", None) => ParameterDefault::Float(
                 default
                     .parse()
                     .map_err(|_| format!("invalid value float {default}"))?,
             ),
-            "string" => ParameterDefault::String(default.to_string()),
+            ("string", None) => ParameterDefault::String(default.to_string()),
+            ("enum", Some(variants)) => ParameterDefault::enumeration(variants, default)?,
+            ("enum", None) => return Err("expected enum(value,value...)".to_string()),
+            ("list", variants) => ParameterDefault::list(variants, default)?,
+            (_, Some(_)) => return Err(format!("parameter type {parameter_type} takes no (...)")),
             _ => return Err(format!("invalid parameter type {parameter_type}")),
         };
 
         Ok(default)
     }
 }
+impl ParameterDefault {
+    pub(crate) fn int_range(min: i32, max: i32, default: i32) -> Result<Self, String> {
+        if !(min..=max).contains(&default) {
+            return Err(format!("default {default} is not between {min} and {max}"));
+        }
+        Ok(ParameterDefault::IntRange { min, max, default })
+    }
+    pub(crate) fn enumeration(variants: Vec<String>, default: &str) -> Result<Self, String> {
+        let default = default.trim().to_string();
+        if !variants.contains(&default) {
+            return Err(format!(
+                "default {default} is not one of {}",
+                variants.join(", ")
+            ));
+        }
+        Ok(ParameterDefault::Enum { variants, default })
+    }
+    pub(crate) fn list(variants: Option<Vec<String>>, default: &str) -> Result<Self, String> {
+        let default = split_list(default).map(str::to_string).collect::<Vec<_>>();
+        if let Some(variants) = &variants {
+            if let Some(invalid) = default.iter().find(|value| !variants.contains(value)) {
+                return Err(format!(
+                    "default {invalid} is not one of {}",
+                    variants.join(", ")
+                ));
+            }
+        }
+        Ok(ParameterDefault::List { variants, default })
+    }
+}
 
 impl fmt::Display for ParameterDefault {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             ParameterDefault::Bool(bool) => write!(f, "{bool}"),
             ParameterDefault::Int(i32) => write!(f, "{i32}"),
+            ParameterDefault::IntRange { default, .. } => write!(f, "{default}"),
             ParameterDefault::Float(f32) => write!(f, "{f32}"),
             ParameterDefault::String(string) => write!(f, "{string}"),
+            ParameterDefault::Enum { default, .. } => write!(f, "{default}"),
+            ParameterDefault::List { default, .. } => write!(f, "{}", default.join(",")),
         }
     }
 }
@@ -2,9 +2,12 @@ use std::str::FromStr;
 
 use wotw_seedgen_derive::FromStr;
 
+use crate::uber_state::VUberStateTrigger;
 use crate::VItem;
 
-use crate::header::{GoalmodeHack, HeaderCommand, ParameterDefault, ParameterType, VString, V};
+use crate::header::{
+    GoalmodeHack, HeaderCommand, ParameterCondition, ParameterDefault, ParameterType, VString, V,
+};
 use crate::languages::TokenKind;
 
 use super::{
@@ -31,6 +34,7 @@ enum HeaderCommandKind {
     EndIf,
     #[Ident = "__goalmode_hack"]
     GoalmodeHack,
+    Goal,
 }
 
 impl HeaderCommand {
@@ -51,6 +55,7 @@ impl HeaderCommand {
             HeaderCommandKind::StartIf => parse_if(parser),
             HeaderCommandKind::EndIf => Ok(HeaderCommand::EndIf),
             HeaderCommandKind::GoalmodeHack => parse_goalmode(parser),
+            HeaderCommandKind::Goal => parse_goal(parser),
         }
     }
 }
@@ -148,18 +153,78 @@ fn parse_parameter(parser: &mut Parser) -> Result<HeaderCommand, ParseError> {
     let identifier = parse_ident!(parser, Suggestion::Identifier)?;
     parser.eat_or_suggest(TokenKind::Whitespace, Suggestion::Identifier)?;
     let parameter_type = parse_ident!(parser, Suggestion::ParameterType)?;
+    let variants_start = parser.current_token().range.start;
+    let variants = if parser.current_token().kind == TokenKind::OpenParen {
+        Some(parse_parameter_variants(parser)?)
+    } else {
+        None
+    };
+    let variants_range = variants_start..parser.current_token().range.start;
     parser.eat_or_suggest(TokenKind::Colon, Suggestion::ParameterType)?;
-    let default = match parameter_type {
-        ParameterType::Bool => ParameterDefault::Bool(parse_ident!(parser, Suggestion::Boolean)?),
-        ParameterType::Int => ParameterDefault::Int(parse_number!(parser, Suggestion::Integer)?),
-        ParameterType::Float => ParameterDefault::Float(parse_number!(parser, Suggestion::Float)?),
-        ParameterType::String => ParameterDefault::String(parse_string(parser).to_owned()),
+    let default_start = parser.current_token().range.start;
+    let default = match (parameter_type, variants) {
+        (ParameterType::Bool, None) => {
+            ParameterDefault::Bool(parse_ident!(parser, Suggestion::Boolean)?)
+        }
+        (ParameterType::Int, None) => {
+            ParameterDefault::Int(parse_number!(parser, Suggestion::Integer)?)
+        }
+        (ParameterType::Int, Some(bounds)) => {
+            let (min, max) = match &bounds[..] {
+                [min, max] => (min.parse().ok(), max.parse().ok()),
+                _ => (None, None),
+            };
+            let (min, max) = min
+                .zip(max)
+                .ok_or_else(|| parser.error("expected int(min,max)", variants_range.clone()))?;
+            let default = parse_number!(parser, Suggestion::Integer)?;
+            ParameterDefault::int_range(min, max, default).map_err(|err| {
+                parser.error(err, default_start..parser.current_token().range.start)
+            })?
+        }
+        (ParameterType::Float, None) => {
+            ParameterDefault::Float(parse_number!(parser, Suggestion::Float)?)
+        }
+        (ParameterType::String, None) => ParameterDefault::String(parse_string(parser).to_owned()),
+        (ParameterType::Enum, Some(variants)) => {
+            let default = parse_string(parser).to_owned();
+            ParameterDefault::enumeration(variants, &default)
+                .map_err(|err| parser.error(err, default_start..default_start + default.len()))?
+        }
+        (ParameterType::Enum, None) => {
+            return Err(parser.error("expected enum(value,value...)", variants_range))
+        }
+        (ParameterType::List, variants) => {
+            let default = parse_string(parser).to_owned();
+            ParameterDefault::list(variants, &default)
+                .map_err(|err| parser.error(err, default_start..default_start + default.len()))?
+        }
+        (_, Some(_)) => {
+            return Err(parser.error("this parameter type takes no (...)", variants_range))
+        }
     };
     Ok(HeaderCommand::Parameter {
         identifier,
         default,
     })
 }
+/// Parses the allowed values of a parameter, like `(easy,normal,hard)`
+fn parse_parameter_variants(parser: &mut Parser) -> Result<Vec<String>, ParseError> {
+    parser.eat(TokenKind::OpenParen)?;
+    let start = parser.current_token().range.start;
+    parser.skip_while(|kind| {
+        !matches!(
+            kind,
+            TokenKind::CloseParen | TokenKind::Newline | TokenKind::Eof
+        )
+    });
+    let end = parser.current_token().range.start;
+    parser.eat(TokenKind::CloseParen)?;
+
+    Ok(crate::header::split_list(parser.read(start..end))
+        .map(str::to_owned)
+        .collect())
+}
 fn parse_set(parser: &mut Parser) -> Result<HeaderCommand, ParseError> {
     parser.eat_or_suggest(TokenKind::Whitespace, Suggestion::HeaderCommand)?;
     let mut state = String::new();
@@ -180,8 +245,31 @@ fn parse_if(parser: &mut Parser) -> Result<HeaderCommand, ParseError> {
     let parameter = parse_ident!(parser, Suggestion::Identifier)?;
     parser.eat_or_suggest(TokenKind::Whitespace, Suggestion::Identifier)?;
     let token = parser.next_token();
-    let value = parser.read_token(&token).to_owned();
-    Ok(HeaderCommand::If { parameter, value })
+    let condition = match token.kind {
+        TokenKind::Greater => {
+            parser.skip(TokenKind::Whitespace);
+            ParameterCondition::Greater(parse_number!(parser, Suggestion::Float)?)
+        }
+        TokenKind::Less => {
+            parser.skip(TokenKind::Whitespace);
+            ParameterCondition::Less(parse_number!(parser, Suggestion::Float)?)
+        }
+        TokenKind::Identifier
+            if parser.read_token(&token) == "in"
+                && parser.current_token().kind == TokenKind::Whitespace =>
+        {
+            parser.next_token();
+            let values = crate::header::split_list(parse_string(parser))
+                .map(str::to_owned)
+                .collect();
+            ParameterCondition::In(values)
+        }
+        _ => ParameterCondition::Equals(parser.read_token(&token).to_owned()),
+    };
+    Ok(HeaderCommand::If {
+        parameter,
+        condition,
+    })
 }
 #[derive(FromStr)]
 #[ParseFromIdentifier]
@@ -209,3 +297,18 @@ fn parse_goalmode(parser: &mut Parser) -> Result<HeaderCommand, ParseError> {
 
     Ok(HeaderCommand::GoalmodeHack(goal))
 }
+fn parse_goal(parser: &mut Parser) -> Result<HeaderCommand, ParseError> {
+    parser.eat_or_suggest(TokenKind::Whitespace, Suggestion::HeaderCommand)?;
+
+    let mut conditions = vec![VUberStateTrigger::parse(parser)?];
+    while parser.current_token().kind == TokenKind::Comma {
+        parser.next_token();
+        parser.skip(TokenKind::Whitespace);
+        conditions.push(VUberStateTrigger::parse(parser)?);
+    }
+
+    parser.eat(TokenKind::Whitespace)?;
+    let flag = VString(parse_string(parser).to_owned());
+
+    Ok(HeaderCommand::Goal { conditions, flag })
+}
@@ -583,4 +583,77 @@ mod tests {
         assert!(Item::from_str("7|3").is_err());
         assert!(Item::from_str("-0|65").is_err());
     }
+
+    #[test]
+    fn typed_parameters() {
+        use crate::header::{Header, HeaderCommand, ParameterCondition, ParameterDefault};
+        use rustc_hash::FxHashMap;
+
+        assert_eq!(
+            HeaderCommand::from_str("parameter amount int(0,10):3")
+                .ok()
+                .and_then(|command| match command {
+                    HeaderCommand::Parameter { default, .. } => Some(default),
+                    _ => None,
+                }),
+            Some(ParameterDefault::IntRange {
+                min: 0,
+                max: 10,
+                default: 3
+            })
+        );
+        assert!(HeaderCommand::from_str("parameter amount int(0,10):11").is_err());
+        assert!(HeaderCommand::from_str("parameter mode enum(a,b):c").is_err());
+        assert!(HeaderCommand::from_str("parameter mode enum:a").is_err());
+        assert!(HeaderCommand::from_str("parameter zones list(Marsh,Glades):Woods").is_err());
+        assert!(HeaderCommand::from_str("parameter fun bool(a):true").is_err());
+        assert!(matches!(
+            HeaderCommand::from_str("if zones in Marsh, Glades"),
+            Ok(HeaderCommand::If {
+                condition: ParameterCondition::In(values),
+                ..
+            }) if values == ["Marsh", "Glades"]
+        ));
+
+        let header = "\
+!!parameter amount int(0,10):3
+!!parameter mode enum(easy,normal,hard):normal
+!!parameter zones list(Marsh,Glades,Woods):Marsh,Woods
+!!if amount > 2
+!!set High
+!!endif
+!!if amount < 2
+!!set Low
+!!endif
+!!if mode in easy,normal
+!!set Casual
+!!endif
+!!if zones in Glades
+!!set Glades
+!!endif
+!!if zones in Woods
+!!set Woods
+!!endif
+";
+        let header = Header::parse(header.to_string(), &mut rand::thread_rng()).unwrap();
+        let build = |parameters: &[(&str, &str)]| {
+            let parameters = parameters
+                .iter()
+                .map(|(identifier, value)| (identifier.to_string(), value.to_string()))
+                .collect::<FxHashMap<_, _>>();
+            header
+                .clone()
+                .build(parameters)
+                .map(|build| build.state_sets)
+        };
+
+        assert_eq!(build(&[]).unwrap(), ["High", "Casual", "Woods"]);
+        assert_eq!(
+            build(&[("amount", "0"), ("mode", "hard"), ("zones", "Glades")]).unwrap(),
+            ["Low", "Glades"]
+        );
+        assert!(build(&[("amount", "11")]).is_err());
+        assert!(build(&[("mode", "medium")]).is_err());
+        assert!(build(&[("zones", "Marsh,Hollow")]).is_err());
+    }
 }
@@ -0,0 +1,387 @@
+use rand::rngs::StdRng;
+use rand_seeder::Seeder;
+use rustc_hash::FxHashMap;
+
+use std::str::FromStr;
+
+use crate::Item;
+
+use super::{
+    parser::{self, parse_header_contents},
+    Header, HeaderBuild, HeaderContent, Pickup, VResolve,
+};
+
+/// Test cases for a header, asserting on the [`HeaderBuild`] it produces with the given parameters
+///
+/// # Syntax
+///
+/// ```text
+/// // Comments span the whole line
+/// test default parameters
+/// expect flag Teleporters
+/// expect pool 5|0 1
+///
+/// test without burrows
+/// parameter burrows=false
+/// expect pool 5|0 0
+/// expect no preplacement 3|0|5|0
+///
+/// test unknown parameter
+/// parameter nonsense=true
+/// expect error
+/// ```
+///
+/// Every `test` line starts a new test case, `parameter` lines set the parameters for the current test case
+///
+/// Available expectations are:
+/// - `expect flag <flag>`
+/// - `expect preplacement <pickup>`
+/// - `expect pool <item> <amount>`, where `<amount>` is the total change to the item pool
+/// - `expect set <state>`
+/// - `expect include <header>`
+/// - `expect exclude <header>`
+/// - `expect error`, if building the header should fail
+///
+/// All expectations except `pool` and `error` may be negated as `expect no ...`
+#[derive(Debug, Clone)]
+pub struct HeaderTest {
+    pub cases: Vec<TestCase>,
+}
+
+/// One test case of a [`HeaderTest`]
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    pub name: String,
+    /// Parameters to build the header with, missing parameters use their default value
+    pub parameters: FxHashMap<String, String>,
+    pub expectations: Vec<Expectation>,
+}
+
+/// An assertion on the [`HeaderBuild`] of a [`TestCase`]
+///
+/// `present` is `false` for negated expectations
+#[derive(Debug, Clone)]
+pub enum Expectation {
+    /// Building the header should fail
+    Error,
+    Flag {
+        flag: String,
+        present: bool,
+    },
+    Preplacement {
+        pickup: Pickup,
+        present: bool,
+    },
+    /// The item pool should change by exactly `amount`
+    PoolChange {
+        item: Item,
+        amount: i32,
+    },
+    StateSet {
+        state: String,
+        present: bool,
+    },
+    Include {
+        name: String,
+        present: bool,
+    },
+    Exclude {
+        name: String,
+        present: bool,
+    },
+}
+
+/// Outcome of running a [`TestCase`]
+#[derive(Debug, Clone)]
+pub struct TestResult {
+    pub name: String,
+    /// Descriptions of all expectations that weren't met
+    pub failures: Vec<String>,
+}
+impl TestResult {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl HeaderTest {
+    /// Parse header test syntax
+    pub fn parse(input: &str) -> Result<HeaderTest, String> {
+        let mut cases = Vec::<TestCase>::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+
+            let (keyword, arguments) = line.split_once(' ').unwrap_or((line, ""));
+            let arguments = arguments.trim();
+            if keyword == "test" {
+                cases.push(TestCase {
+                    name: arguments.to_string(),
+                    parameters: FxHashMap::default(),
+                    expectations: vec![],
+                });
+                continue;
+            }
+
+            let case = cases
+                .last_mut()
+                .ok_or_else(|| format!("line {}: expected a test case", index + 1))?;
+            match keyword {
+                "parameter" => {
+                    let (identifier, value) = arguments.split_once('=').ok_or_else(|| {
+                        format!("line {}: expected <parameter>=<value>", index + 1)
+                    })?;
+                    case.parameters
+                        .insert(identifier.trim().to_string(), value.trim().to_string());
+                }
+                "expect" => {
+                    let expectation = Expectation::parse(arguments)
+                        .map_err(|err| format!("line {}: {err}", index + 1))?;
+                    case.expectations.push(expectation);
+                }
+                _ => {
+                    return Err(format!(
+                        "line {}: expected test, parameter or expect",
+                        index + 1
+                    ))
+                }
+            }
+        }
+
+        Ok(HeaderTest { cases })
+    }
+
+    /// Run all test cases against the header syntax
+    pub fn run(&self, header: &str) -> Vec<TestResult> {
+        self.cases.iter().map(|case| case.run(header)).collect()
+    }
+}
+
+impl TestCase {
+    /// Run this test case against the header syntax
+    ///
+    /// `!!take` commands are evaluated with an rng seeded by the test case's name to keep results reproducible
+    pub fn run(&self, header: &str) -> TestResult {
+        let mut rng: StdRng = Seeder::from(&self.name).make_rng();
+        let build = Header::parse(header.to_string(), &mut rng)
+            .map_err(|errors| errors.verbose_display())
+            .and_then(|header| header.build(self.parameters.clone()));
+
+        let expect_error = self
+            .expectations
+            .iter()
+            .any(|expectation| matches!(expectation, Expectation::Error));
+        let failures = match build {
+            Ok(build) if expect_error => vec![format!(
+                "Expected an error, but the header built successfully with {} preplacements",
+                build.preplacements.len()
+            )],
+            Ok(build) => self
+                .expectations
+                .iter()
+                .filter_map(|expectation| expectation.check(&build))
+                .collect(),
+            Err(_) if expect_error => vec![],
+            Err(err) => vec![format!("Failed to build the header: {err}")],
+        };
+
+        TestResult {
+            name: self.name.clone(),
+            failures,
+        }
+    }
+}
+
+impl Expectation {
+    fn parse(input: &str) -> Result<Expectation, String> {
+        let (present, input) = match input.strip_prefix("no ") {
+            Some(input) => (false, input.trim_start()),
+            None => (true, input),
+        };
+        let (kind, argument) = input.split_once(' ').unwrap_or((input, ""));
+        let argument = argument.trim();
+
+        let expectation = match kind {
+            "error" => Expectation::Error,
+            "flag" => Expectation::Flag {
+                flag: argument.to_string(),
+                present,
+            },
+            "preplacement" => Expectation::Preplacement {
+                pickup: parse_pickup(argument)?,
+                present,
+            },
+            "pool" => {
+                let (item, amount) = argument
+                    .rsplit_once(' ')
+                    .ok_or("expected pool <item> <amount>")?;
+                Expectation::PoolChange {
+                    item: Item::from_str(item.trim())?,
+                    amount: amount
+                        .parse()
+                        .map_err(|_| format!("invalid amount {amount}"))?,
+                }
+            }
+            "set" => Expectation::StateSet {
+                state: argument.to_string(),
+                present,
+            },
+            "include" => Expectation::Include {
+                name: argument.to_string(),
+                present,
+            },
+            "exclude" => Expectation::Exclude {
+                name: argument.to_string(),
+                present,
+            },
+            _ => {
+                return Err(format!(
+                    "unknown expectation {kind}, expected flag, preplacement, pool, set, include, exclude or error"
+                ))
+            }
+        };
+
+        if !present
+            && matches!(
+                expectation,
+                Expectation::Error | Expectation::PoolChange { .. }
+            )
+        {
+            return Err(format!("expect no {kind} is not supported"));
+        }
+        if !matches!(expectation, Expectation::Error) && argument.is_empty() {
+            return Err(format!("expected an argument after {kind}"));
+        }
+
+        Ok(expectation)
+    }
+
+    /// Returns a description of the failure if the expectation isn't met
+    fn check(&self, build: &HeaderBuild) -> Option<String> {
+        let (found, description) = match self {
+            Expectation::Error => return None,
+            Expectation::Flag { flag, .. } => {
+                (build.flags.contains(flag), format!("flag \"{flag}\""))
+            }
+            Expectation::Preplacement { pickup, .. } => {
+                let code = pickup.code().to_string();
+                (
+                    build
+                        .preplacements
+                        .iter()
+                        .any(|preplacement| preplacement.code().to_string() == code),
+                    format!("preplacement {code}"),
+                )
+            }
+            Expectation::PoolChange { item, amount } => {
+                let actual = build
+                    .item_pool_changes
+                    .get(item)
+                    .copied()
+                    .unwrap_or_default();
+                return (actual != *amount).then(|| {
+                    format!(
+                        "Expected the item pool to change {} by {amount}, but it changed by {actual}",
+                        item.code()
+                    )
+                });
+            }
+            Expectation::StateSet { state, .. } => (
+                build.state_sets.contains(state),
+                format!("state set \"{state}\""),
+            ),
+            Expectation::Include { name, .. } => {
+                (build.includes.contains(name), format!("include of {name}"))
+            }
+            Expectation::Exclude { name, .. } => {
+                (build.excludes.contains(name), format!("exclude of {name}"))
+            }
+        };
+        let present = self.present();
+
+        (found != present).then(|| {
+            if present {
+                format!("Expected {description}")
+            } else {
+                format!("Expected no {description}")
+            }
+        })
+    }
+
+    fn present(&self) -> bool {
+        match self {
+            Expectation::Error | Expectation::PoolChange { .. } => true,
+            Expectation::Flag { present, .. }
+            | Expectation::Preplacement { present, .. }
+            | Expectation::StateSet { present, .. }
+            | Expectation::Include { present, .. }
+            | Expectation::Exclude { present, .. } => *present,
+        }
+    }
+}
+
+fn parse_pickup(input: &str) -> Result<Pickup, String> {
+    let mut parser = parser::new(input);
+    let mut contents =
+        parse_header_contents(&mut parser).map_err(|errors| errors.verbose_display())?;
+    match (contents.pop(), contents.is_empty()) {
+        (Some(HeaderContent::Pickup(pickup)), true) => pickup.resolve(&FxHashMap::default()),
+        _ => Err(format!("expected a pickup, found {input}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "\
+Flags: Test
+!!parameter extra int:2
+!!parameter launch bool:true
+!!add $PARAM(extra)x 10|30
+!!if launch true
+3|0|2|8
+!!set Launch
+!!endif
+";
+
+    #[test]
+    fn header_tests() {
+        let test = HeaderTest::parse(
+            "\
+// Comment
+test defaults
+expect flag Test
+expect pool 10|30 2
+expect preplacement 3|0|2|8
+expect set Launch
+expect no include bonus_items
+
+test without launch
+parameter launch=false
+parameter extra=5
+expect pool 10|30 5
+expect no preplacement 3|0|2|8
+expect set Launch
+
+test unknown parameter
+parameter nonsense=true
+expect error
+",
+        )
+        .unwrap();
+        assert_eq!(test.cases.len(), 3);
+
+        let results = test.run(HEADER);
+        assert!(results[0].passed(), "{:?}", results[0].failures);
+        assert_eq!(results[1].failures, ["Expected state set \"Launch\""]);
+        assert!(results[2].passed(), "{:?}", results[2].failures);
+
+        assert!(HeaderTest::parse("expect error").is_err());
+        assert!(HeaderTest::parse("test a\nexpect no pool 10|30 1").is_err());
+        assert!(HeaderTest::parse("test a\nexpect preplacement 3|0").is_err());
+    }
+}
@@ -0,0 +1,409 @@
+//! Tools to inspect logic syntax while it is being written, as used by language servers
+//!
+//! All offsets are byte offsets into the areas source
+
+use std::ops::Range;
+
+use rustc_hash::FxHashMap;
+
+use super::parser::{AreaContent, Areas, Group, Line, RequirementValue, Skill};
+use crate::{
+    languages::{
+        analysis::{line_at, Completion, CompletionKind, Hover},
+        ParseError,
+    },
+    settings::Trick,
+    util::NodeKind,
+};
+
+/// Returns all errors in the logic syntax
+pub fn diagnostics(input: &str) -> Vec<ParseError> {
+    let (_, errors) = Areas::parse_recovering(input);
+    errors.to_vec()
+}
+
+/// Returns the possible completions at `offset`
+///
+/// Anchor identifiers are completed after `conn` and `door`, skills, tricks and named requirements where a requirement is expected
+pub fn completions(input: &str, offset: usize) -> Vec<Completion> {
+    let (start, line) = line_at(input, offset);
+    let before = &line[..(offset - start).min(line.len())];
+    if before.contains('#') {
+        return vec![];
+    }
+    let word_start = before
+        .rfind(|c: char| !is_ident_char(c))
+        .map_or(0, |index| index + 1);
+    let prefix = before[..word_start].trim();
+
+    let (areas, _) = Areas::parse_recovering(input);
+    let macros = macros(&areas);
+
+    if prefix == "conn" || prefix == "door" {
+        areas
+            .contents
+            .iter()
+            .filter_map(|content| match content {
+                AreaContent::Anchor(anchor) => Some(Completion {
+                    label: anchor.identifier.to_string(),
+                    kind: CompletionKind::Anchor,
+                    detail: None,
+                    documentation: None,
+                }),
+                _ => None,
+            })
+            .collect()
+    } else if prefix.ends_with(':')
+        || prefix.ends_with(',')
+        || prefix.ends_with(" OR")
+        // Lines nested inside a difficulty contain requirements right away
+        || (prefix.is_empty() && line.len() - line.trim_start().len() >= 4)
+    {
+        let skills = Skill::ALL.into_iter().map(|skill| Completion {
+            label: format!("{skill:?}"),
+            kind: CompletionKind::Skill,
+            detail: Some("Skill".to_string()),
+            documentation: None,
+        });
+        let tricks = Trick::ALL.into_iter().map(|trick| Completion {
+            label: format!("{trick:?}"),
+            kind: CompletionKind::Trick,
+            detail: Some("Trick".to_string()),
+            documentation: None,
+        });
+        let requirements = macros.keys().map(|name| Completion {
+            label: name.to_string(),
+            kind: CompletionKind::Requirement,
+            detail: Some("Named requirement".to_string()),
+            documentation: Some(expand_macro(name, &macros, input)),
+        });
+        skills.chain(tricks).chain(requirements).collect()
+    } else {
+        vec![]
+    }
+}
+
+/// Returns the expanded group of the named requirement at `offset`
+///
+/// Named requirements used inside the group are expanded as well
+pub fn hover(input: &str, offset: usize) -> Option<Hover> {
+    let (areas, _) = Areas::parse_recovering(input);
+    let macros = macros(&areas);
+
+    let (name, range) = areas.contents.iter().find_map(|content| match content {
+        AreaContent::Requirement(named_group) if contains(&named_group.range, offset) => {
+            Some((named_group.name, named_group.range.clone()))
+        }
+        _ => content_groups(content)
+            .into_iter()
+            .find_map(|group| macro_in_group(group, offset)),
+    })?;
+
+    Some(Hover {
+        range,
+        text: expand_macro(name, &macros, input),
+    })
+}
+
+/// Returns all places where the anchor at `offset` is declared or connected to
+///
+/// The declaration is only included if `include_declaration` is `true`
+pub fn references(input: &str, offset: usize, include_declaration: bool) -> Vec<Range<usize>> {
+    let (areas, _) = Areas::parse_recovering(input);
+    let anchors = areas
+        .contents
+        .iter()
+        .filter_map(|content| match content {
+            AreaContent::Anchor(anchor) => Some(anchor),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let connections = anchors
+        .iter()
+        .flat_map(|anchor| &anchor.connections)
+        .filter(|connection| connection.kind == NodeKind::Anchor)
+        .collect::<Vec<_>>();
+
+    let identifier = match anchors
+        .iter()
+        .find(|anchor| contains(&anchor.range, offset))
+        .map(|anchor| anchor.identifier)
+        .or_else(|| {
+            connections
+                .iter()
+                .find(|connection| contains(&connection.range, offset))
+                .map(|connection| connection.identifier)
+        }) {
+        Some(identifier) => identifier,
+        None => return vec![],
+    };
+
+    let declarations = anchors
+        .iter()
+        .filter(|anchor| include_declaration && anchor.identifier == identifier)
+        .map(|anchor| anchor.range.clone());
+    let references = connections
+        .iter()
+        .filter(|connection| connection.identifier == identifier)
+        .map(|connection| connection.range.clone());
+    declarations.chain(references).collect()
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.'
+}
+
+fn contains(range: &Range<usize>, offset: usize) -> bool {
+    (range.start..=range.end).contains(&offset)
+}
+
+fn macros<'a, 'b>(areas: &'b Areas<'a>) -> FxHashMap<&'a str, &'b Group<'a>> {
+    areas
+        .contents
+        .iter()
+        .filter_map(|content| match content {
+            AreaContent::Requirement(named_group) => Some((named_group.name, &named_group.group)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn content_groups<'a, 'b>(content: &'b AreaContent<'a>) -> Vec<&'b Group<'a>> {
+    match content {
+        AreaContent::Requirement(named_group) | AreaContent::Region(named_group) => {
+            vec![&named_group.group]
+        }
+        AreaContent::Anchor(anchor) => anchor
+            .teleport_restriction
+            .iter()
+            .chain(
+                anchor
+                    .refills
+                    .iter()
+                    .flat_map(|refill| &refill.requirements),
+            )
+            .chain(
+                anchor
+                    .connections
+                    .iter()
+                    .map(|connection| &connection.requirements),
+            )
+            .collect(),
+    }
+}
+
+fn macro_in_group<'a>(group: &Group<'a>, offset: usize) -> Option<(&'a str, Range<usize>)> {
+    group.lines.iter().find_map(|line| {
+        line.ands
+            .iter()
+            .chain(&line.ors)
+            .find_map(|requirement| match requirement.value {
+                RequirementValue::Macro(name) if contains(&requirement.range, offset) => {
+                    Some((name, requirement.range.clone()))
+                }
+                _ => None,
+            })
+            .or_else(|| {
+                line.group
+                    .as_ref()
+                    .and_then(|group| macro_in_group(group, offset))
+            })
+    })
+}
+
+/// Writes the named requirement in areas syntax, followed by all named requirements it uses
+fn expand_macro<'a>(name: &'a str, macros: &FxHashMap<&'a str, &Group<'a>>, input: &str) -> String {
+    let mut text = String::new();
+    let mut expanded = vec![name];
+    let mut index = 0;
+    while let Some(name) = expanded.get(index).copied() {
+        if let Some(group) = macros.get(name) {
+            text.push_str(&format!("requirement {name}:"));
+            write_group(group, 1, input, &mut text, &mut expanded);
+        }
+        index += 1;
+    }
+
+    text
+}
+
+fn write_group<'a>(
+    group: &Group<'a>,
+    depth: usize,
+    input: &str,
+    text: &mut String,
+    expanded: &mut Vec<&'a str>,
+) {
+    // Groups with a single line can be written after the colon
+    if let [line] = &group.lines[..] {
+        if line.group.is_none() {
+            text.push(' ');
+            write_requirements(line, input, text, expanded);
+            text.push('\n');
+            return;
+        }
+    }
+
+    text.push('\n');
+    for line in &group.lines {
+        text.push_str(&"  ".repeat(depth));
+        write_requirements(line, input, text, expanded);
+        match &line.group {
+            Some(group) => {
+                text.push(':');
+                write_group(group, depth + 1, input, text, expanded);
+            }
+            None => text.push('\n'),
+        }
+    }
+}
+
+fn write_requirements<'a>(
+    line: &Line<'a>,
+    input: &str,
+    text: &mut String,
+    expanded: &mut Vec<&'a str>,
+) {
+    let ands = line.ands.iter().map(|and| &input[and.range.clone()]);
+    let ors = line.ors.iter().map(|or| &input[or.range.clone()]);
+    let mut parts = ands.collect::<Vec<_>>();
+    let ors = ors.collect::<Vec<_>>().join(" OR ");
+    if !ors.is_empty() {
+        parts.push(&ors);
+    }
+    text.push_str(&parts.join(", "));
+
+    for requirement in line.ands.iter().chain(&line.ors) {
+        if let RequirementValue::Macro(name) = requirement.value {
+            if !expanded.contains(&name) {
+                expanded.push(name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREAS: &str = "
+requirement Helper:
+  moki: Bash
+
+requirement Used:
+  moki: DoubleJump, Helper
+  gorlek:
+    Launch OR Glide
+
+anchor MarshSpawn.Main at 0, 0:
+  conn RoomA:
+    moki: Used
+  pickup MarshSpawn.RockHC: free
+
+anchor RoomA at 10, 0:
+  conn MarshSpawn.Main: free
+  door MarshSpawn.Main: free
+";
+
+    fn offset_of(pattern: &str) -> usize {
+        AREAS.find(pattern).unwrap()
+    }
+
+    #[test]
+    fn logic_diagnostics() {
+        let errors = diagnostics(AREAS);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let input = "
+anchor A:
+  conn B:
+    moki: Bash ,, Launch
+    gorlek: Unknown
+  conn C: free free
+
+anchor B:
+  conn A free
+  conn C: free
+";
+        let errors = diagnostics(input);
+        assert_eq!(errors.len(), 4, "{errors:?}");
+        let (areas, _) = Areas::parse_recovering(input);
+        let connections = areas
+            .contents
+            .iter()
+            .map(|content| match content {
+                AreaContent::Anchor(anchor) => anchor.connections.len(),
+                _ => 0,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(connections, [1, 1]);
+    }
+
+    #[test]
+    fn logic_completions() {
+        let labels = |input: &str, offset| {
+            completions(input, offset)
+                .into_iter()
+                .map(|completion| completion.label)
+                .collect::<Vec<_>>()
+        };
+
+        let offset = offset_of("RoomA:\n");
+        assert_eq!(labels(AREAS, offset), ["MarshSpawn.Main", "RoomA"]);
+
+        let offset = offset_of("DoubleJump");
+        let requirements = labels(AREAS, offset);
+        assert_eq!(
+            requirements.len(),
+            Skill::ALL.len() + Trick::ALL.len() + 2,
+            "{requirements:?}"
+        );
+        assert!(requirements.contains(&"Bash".to_string()));
+        assert!(requirements.contains(&"Helper".to_string()));
+
+        assert_eq!(
+            labels(AREAS, offset_of("anchor RoomA")),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn logic_hover() {
+        let expected = "\
+requirement Used:
+  moki: DoubleJump, Helper
+  gorlek: Launch OR Glide
+requirement Helper:
+  moki: Bash
+";
+        let hover = hover(AREAS, offset_of("Used\n")).unwrap();
+        assert_eq!(&AREAS[hover.range], "Used");
+        assert_eq!(hover.text, expected);
+        assert_eq!(
+            super::hover(AREAS, offset_of("Used:")).unwrap().text,
+            expected
+        );
+        assert_eq!(super::hover(AREAS, offset_of("Bash")), None);
+    }
+
+    #[test]
+    fn anchor_references() {
+        let text = |ranges: Vec<Range<usize>>| {
+            ranges
+                .into_iter()
+                .map(|range| (&AREAS[range.clone()], range.start))
+                .collect::<Vec<_>>()
+        };
+
+        let references = references(AREAS, offset_of("MarshSpawn.Main at"), true);
+        assert_eq!(references.len(), 3);
+        assert!(text(references)
+            .iter()
+            .all(|(identifier, _)| *identifier == "MarshSpawn.Main"));
+
+        let references = super::references(AREAS, offset_of("RoomA:"), false);
+        assert_eq!(text(references), [("RoomA", offset_of("RoomA:"))]);
+
+        assert!(super::references(AREAS, offset_of("Bash"), true).is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt::{self, Display};
+
+use itertools::Itertools;
+use rustc_hash::FxHashSet;
+use smallvec::smallvec;
+
+use super::{build, parse_locations, parse_states, Areas};
+use crate::{
+    inventory::Inventory,
+    settings::{Difficulty, UniverseSettings, WorldSettings},
+    world::{graph::Node, requirement::filter_redundancies, Graph, Player},
+};
+
+/// Item slots available to a single solution, this is high enough to never limit the solutions
+const SLOTS: usize = 1000;
+
+/// Differences in how locations can be reached between two versions of the logic, found by [`diff_logic`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogicDiff {
+    pub difficulties: Vec<DifficultyDiff>,
+}
+/// Differences between two versions of the logic on one [`Difficulty`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DifficultyDiff {
+    pub difficulty: Difficulty,
+    /// Locations that changed, in the order of the pickup locations
+    pub locations: Vec<LocationDiff>,
+}
+/// Changed solutions for a single location
+///
+/// Each solution is a set of items that is needed in addition to the items the player spawns with
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocationDiff {
+    pub identifier: String,
+    /// Solutions that only the old logic had
+    pub removed: Vec<Inventory>,
+    /// Solutions that only the new logic has
+    pub added: Vec<Inventory>,
+}
+
+impl LogicDiff {
+    /// Checks whether no location changed on any [`Difficulty`]
+    pub fn is_empty(&self) -> bool {
+        self.difficulties
+            .iter()
+            .all(|difficulty| difficulty.locations.is_empty())
+    }
+}
+
+impl Display for LogicDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for difficulty in &self.difficulties {
+            writeln!(f, "{}:", difficulty.difficulty)?;
+            if difficulty.locations.is_empty() {
+                writeln!(f, "  No changes")?;
+            }
+            for location in &difficulty.locations {
+                writeln!(f, "  {}", location.identifier)?;
+                for solution in &location.removed {
+                    writeln!(f, "    - {}", solution_text(solution))?;
+                }
+                for solution in &location.added {
+                    writeln!(f, "    + {}", solution_text(solution))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn solution_text(solution: &Inventory) -> String {
+    if solution.items.is_empty() {
+        return "free".to_string();
+    }
+
+    // Sort for a stable output since the inventory is stored in a hashmap
+    solution
+        .items
+        .iter()
+        .map(|(item, amount)| {
+            if *amount == 1 {
+                item.to_string()
+            } else {
+                format!("{amount} {item}")
+            }
+        })
+        .sorted()
+        .join(", ")
+}
+
+/// Compares the minimal solutions of every pickup location between two versions of the areas file on each [`Difficulty`]
+///
+/// The solutions only consider the connections from the anchors directly leading to a location, not how those anchors are reached.
+/// This keeps the comparison independent of spawn locations and of changes elsewhere in the logic.
+/// States are assumed to be met and the player is assumed to have full health and energy at the anchor.
+///
+/// `world_settings` provides the tricks and in-game difficulty to use, its [`Difficulty`] is replaced with each one in turn
+pub fn diff_logic(
+    old_areas: &str,
+    new_areas: &str,
+    locations: &str,
+    states: &str,
+    world_settings: &WorldSettings,
+) -> Result<LogicDiff, String> {
+    let old_areas =
+        Areas::parse(old_areas).map_err(|err| format!("Old logic: {}", err.verbose_display()))?;
+    let new_areas =
+        Areas::parse(new_areas).map_err(|err| format!("New logic: {}", err.verbose_display()))?;
+    let locations = parse_locations(locations)?;
+    let named_states = parse_states(states)?;
+
+    let difficulties = [
+        Difficulty::Moki,
+        Difficulty::Gorlek,
+        Difficulty::Kii,
+        Difficulty::Unsafe,
+    ]
+    .into_iter()
+    .map(|difficulty| {
+        let world_settings = WorldSettings {
+            difficulty,
+            ..world_settings.clone()
+        };
+        let universe_settings = UniverseSettings {
+            world_settings: vec![world_settings.clone()],
+            ..UniverseSettings::default()
+        };
+
+        let build_graph = |areas: &Areas| {
+            build(
+                areas.clone(),
+                locations.clone(),
+                named_states.clone(),
+                &universe_settings,
+                false,
+            )
+        };
+        let old_graph = build_graph(&old_areas).map_err(|err| format!("Old logic: {err}"))?;
+        let new_graph = build_graph(&new_areas).map_err(|err| format!("New logic: {err}"))?;
+
+        let locations = locations
+            .iter()
+            .filter_map(|location| {
+                let mut removed = location_solutions(&old_graph, &location.name, &world_settings);
+                let mut added = location_solutions(&new_graph, &location.name, &world_settings);
+                let unchanged = removed
+                    .iter()
+                    .filter(|solution| added.contains(solution))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                removed.retain(|solution| !unchanged.contains(solution));
+                added.retain(|solution| !unchanged.contains(solution));
+
+                if removed.is_empty() && added.is_empty() {
+                    None
+                } else {
+                    Some(LocationDiff {
+                        identifier: location.name.clone(),
+                        removed,
+                        added,
+                    })
+                }
+            })
+            .collect();
+
+        Ok(DifficultyDiff {
+            difficulty,
+            locations,
+        })
+    })
+    .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(LogicDiff { difficulties })
+}
+
+/// Minimal solutions to reach the location from any anchor directly connected to it
+fn location_solutions(
+    graph: &Graph,
+    identifier: &str,
+    world_settings: &WorldSettings,
+) -> Vec<Inventory> {
+    let index = match graph
+        .nodes
+        .iter()
+        .find(|node| node.identifier() == identifier)
+    {
+        Some(node) => node.index(),
+        None => return vec![],
+    };
+
+    let player = Player::spawn(world_settings);
+    let states = graph
+        .nodes
+        .iter()
+        .filter(|node| matches!(node, Node::State(_) | Node::Quest(_)))
+        .map(Node::index)
+        .collect::<FxHashSet<_>>();
+
+    let mut solutions = graph
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Anchor(anchor) => Some(anchor),
+            _ => None,
+        })
+        .flat_map(|anchor| &anchor.connections)
+        .filter(|connection| connection.to == index)
+        .flat_map(|connection| {
+            connection.requirement.solutions(
+                &player,
+                &states,
+                smallvec![player.max_orbs()],
+                SLOTS,
+                SLOTS,
+            )
+        })
+        .collect();
+    filter_redundancies(&mut solutions);
+
+    solutions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::{Item, Skill};
+    use crate::test_fixtures::{LOCATIONS, STATES};
+
+    const OLD_AREAS: &str = "
+anchor MarshSpawn.Main at 0, 0:
+  pickup MarshSpawn.RockHC:
+    moki: Bash
+  pickup MarshSpawn.FirstPickupEX:
+    moki: DoubleJump
+";
+
+    #[test]
+    fn logic_diff() {
+        let world_settings = WorldSettings::default();
+
+        let diff = diff_logic(OLD_AREAS, OLD_AREAS, LOCATIONS, STATES, &world_settings).unwrap();
+        assert!(diff.is_empty(), "{diff}");
+
+        let new_areas =
+            OLD_AREAS.replace("    moki: Bash\n", "    moki: Bash\n    gorlek: Launch\n");
+        let diff = diff_logic(OLD_AREAS, &new_areas, LOCATIONS, STATES, &world_settings).unwrap();
+        assert!(diff.difficulties[0].locations.is_empty(), "{diff}");
+
+        let mut launch = Inventory::default();
+        launch.grant(Item::Skill(Skill::Launch), 1);
+        for difficulty in &diff.difficulties[1..] {
+            assert_eq!(
+                difficulty.locations,
+                [LocationDiff {
+                    identifier: "MarshSpawn.RockHC".to_string(),
+                    removed: vec![],
+                    added: vec![launch.clone()],
+                }]
+            );
+        }
+        assert!(diff.to_string().contains("    + Launch\n"), "{diff}");
+    }
+}
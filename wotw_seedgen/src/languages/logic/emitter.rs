@@ -12,16 +12,17 @@ use crate::{
     settings::{Difficulty, Trick, UniverseSettings},
     util::NodeKind,
     world::{
+        doors::validate_doors,
         graph::{self, Graph, Node},
         requirement::Requirement,
     },
 };
 
-struct EmitterContext<'a> {
-    macros: &'a FxHashMap<&'a str, parser::Group<'a>>,
-    universe_settings: &'a UniverseSettings,
-    node_map: FxHashMap<String, usize>,
-    used_states: FxHashSet<&'a str>,
+pub(super) struct EmitterContext<'a> {
+    pub(super) macros: &'a FxHashMap<&'a str, parser::Group<'a>>,
+    pub(super) universe_settings: &'a UniverseSettings,
+    pub(super) node_map: FxHashMap<String, usize>,
+    pub(super) used_states: FxHashSet<&'a str>,
 }
 
 fn build_trick_requirement(
@@ -87,7 +88,7 @@ fn build_boss_requirement(health: f32, context: &EmitterContext) -> Requirement
     }
 }
 
-fn build_requirement<'a>(
+pub(super) fn build_requirement<'a>(
     requirement: &parser::Requirement<'a>,
     region: bool,
     context: &mut EmitterContext<'a>,
@@ -287,7 +288,7 @@ fn build_requirement<'a>(
     }
 }
 
-fn build_and(mut ands: Vec<Requirement>) -> Requirement {
+pub(super) fn build_and(mut ands: Vec<Requirement>) -> Requirement {
     if ands
         .iter()
         .any(|and| matches!(and, Requirement::Impossible))
@@ -303,7 +304,7 @@ fn build_and(mut ands: Vec<Requirement>) -> Requirement {
     }
     Requirement::And(ands)
 }
-fn build_or(mut ors: Vec<Requirement>) -> Requirement {
+pub(super) fn build_or(mut ors: Vec<Requirement>) -> Requirement {
     if ors.iter().any(|or| matches!(or, Requirement::Free)) {
         return Requirement::Free;
     }
@@ -521,6 +522,7 @@ pub fn build(
             teleport_restriction,
             refills,
             connections,
+            ..
         } = anchor;
         let identifier = identifier.to_owned();
 
@@ -564,7 +566,11 @@ pub fn build(
                     }
                 }
 
-                Ok(graph::Connection { to, requirement })
+                Ok(graph::Connection {
+                    to,
+                    requirement,
+                    door: connection.door,
+                })
             })
             .collect::<Result<Vec<_>, String>>()?;
 
@@ -593,7 +599,10 @@ pub fn build(
         }
     }
 
-    Ok(Graph::new(nodes))
+    let graph = Graph::new(nodes);
+    validate_doors(&graph)?;
+
+    Ok(graph)
 }
 
 #[cfg(test)]
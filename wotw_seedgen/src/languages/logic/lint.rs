@@ -0,0 +1,407 @@
+use std::ops::Range;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use wotw_seedgen_derive::Display;
+
+use super::{
+    emitter::{build_and, build_or, build_requirement, EmitterContext},
+    locations::Location,
+    parser::{AreaContent, Areas, Group, Line, RequirementValue},
+};
+use crate::{
+    languages::ParseError,
+    settings::{Difficulty, Trick, UniverseSettings, WorldSettings},
+    util::{constants::DEFAULT_SPAWN, NodeKind},
+    world::requirement::Requirement,
+};
+
+/// A potential mistake in the logic found by [`lint`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintDiagnostic {
+    pub kind: LintKind,
+    pub message: String,
+    /// Position in the areas source this diagnostic refers to
+    ///
+    /// This is `None` if the diagnostic refers to the pickup locations instead
+    pub range: Option<Range<usize>>,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum LintKind {
+    /// No connection leads to this anchor
+    UnreachableAnchor,
+    /// No anchor connects to this pickup location
+    UnusedLocation,
+    /// This named requirement is never used
+    UnusedRequirement,
+    /// This requirement line can't be met on any difficulty
+    ImpossibleRequirement,
+    /// No anchor defines the state this requirement refers to
+    UnknownState,
+}
+
+impl LintDiagnostic {
+    /// Returns a multiline visual representation of this [`LintDiagnostic`]
+    ///
+    /// `areas` should be the source the diagnostic was created from
+    pub fn verbose_display(&self, areas: &str) -> String {
+        match &self.range {
+            Some(range) => ParseError::new(&self.message, areas, range.clone()).verbose_display(),
+            None => self.message.clone(),
+        }
+    }
+}
+
+/// Checks the logic for mistakes that don't prevent it from being parsed
+///
+/// This finds anchors that no connection leads to, pickup locations that no anchor connects to, named requirements that are never used, states that no anchor defines and requirement lines that can't be met on any difficulty
+///
+/// States that can only be resolved through headers are written as `moki: Impossible`, those lines won't be reported
+pub fn lint(areas: &Areas, locations: &[Location]) -> Vec<LintDiagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let mut macros = FxHashMap::default();
+    let mut anchors = Vec::new();
+    for content in &areas.contents {
+        match content {
+            AreaContent::Requirement(named_group) => {
+                macros.insert(named_group.name, named_group.group.clone());
+            }
+            AreaContent::Region(_) => {}
+            AreaContent::Anchor(anchor) => anchors.push(anchor),
+        }
+    }
+
+    let connection_targets = anchors
+        .iter()
+        .flat_map(|anchor| &anchor.connections)
+        .map(|connection| (connection.kind, connection.identifier))
+        .collect::<FxHashSet<_>>();
+
+    for anchor in &anchors {
+        if anchor.identifier != DEFAULT_SPAWN
+            && !connection_targets.contains(&(NodeKind::Anchor, anchor.identifier))
+        {
+            diagnostics.push(LintDiagnostic {
+                kind: LintKind::UnreachableAnchor,
+                message: format!("No connection leads to anchor {}", anchor.identifier),
+                range: Some(anchor.range.clone()),
+            });
+        }
+    }
+
+    for location in locations {
+        if !connection_targets.contains(&(NodeKind::Pickup, &location.name[..]))
+            && !connection_targets.contains(&(NodeKind::Quest, &location.name[..]))
+        {
+            diagnostics.push(LintDiagnostic {
+                kind: LintKind::UnusedLocation,
+                message: format!("No anchor connects to pickup location {}", location.name),
+                range: None,
+            });
+        }
+    }
+
+    let groups = areas
+        .contents
+        .iter()
+        .flat_map(content_groups)
+        .collect::<Vec<_>>();
+
+    let mut used_macros = FxHashSet::default();
+    for (group, _, _) in &groups {
+        used_macros_in_group(group, &mut used_macros);
+    }
+    for content in &areas.contents {
+        if let AreaContent::Requirement(named_group) = content {
+            if !used_macros.contains(named_group.name) {
+                diagnostics.push(LintDiagnostic {
+                    kind: LintKind::UnusedRequirement,
+                    message: format!("Named requirement {} is never used", named_group.name),
+                    range: Some(named_group.range.clone()),
+                });
+            }
+        }
+    }
+
+    // One world per difficulty with every trick, so a line is only impossible for all of them if no settings allow it
+    let world_settings = [
+        Difficulty::Moki,
+        Difficulty::Gorlek,
+        Difficulty::Kii,
+        Difficulty::Unsafe,
+    ]
+    .into_iter()
+    .map(|difficulty| WorldSettings {
+        difficulty,
+        tricks: Trick::ALL.into_iter().collect(),
+        ..WorldSettings::default()
+    })
+    .collect::<Vec<_>>();
+    let universe_settings = UniverseSettings {
+        world_settings: world_settings.clone(),
+        ..UniverseSettings::default()
+    };
+    // Requirement::State is always possible, so the node indices don't matter
+    let mut node_map = connection_targets
+        .iter()
+        .filter(|(kind, _)| matches!(kind, NodeKind::State | NodeKind::Quest))
+        .map(|(_, identifier)| (identifier.to_string(), 0))
+        .collect::<FxHashMap<_, _>>();
+    let mut unknown_states = Vec::new();
+    for (group, _, _) in &groups {
+        unknown_states_in_group(group, &node_map, &mut unknown_states);
+    }
+    for (state, range) in unknown_states {
+        diagnostics.push(LintDiagnostic {
+            kind: LintKind::UnknownState,
+            message: format!("No anchor defines state {state}"),
+            range: Some(range),
+        });
+        // Still build the requirements to find impossible lines
+        node_map.insert(state.to_string(), 0);
+    }
+    let mut context = EmitterContext {
+        macros: &macros,
+        universe_settings: &universe_settings,
+        node_map,
+        used_states: FxHashSet::default(),
+    };
+
+    for (group, region, header_state) in groups {
+        impossible_lines(
+            group,
+            region,
+            header_state,
+            &Requirement::Free,
+            &mut context,
+            &world_settings,
+            &mut diagnostics,
+        );
+    }
+
+    diagnostics
+}
+
+/// Every requirement group of the content, along with whether it belongs to a region and whether it belongs to a state connection
+fn content_groups<'a, 'b>(content: &'b AreaContent<'a>) -> Vec<(&'b Group<'a>, bool, bool)> {
+    match content {
+        AreaContent::Requirement(named_group) => vec![(&named_group.group, false, false)],
+        AreaContent::Region(named_group) => vec![(&named_group.group, true, false)],
+        AreaContent::Anchor(anchor) => anchor
+            .teleport_restriction
+            .iter()
+            .chain(
+                anchor
+                    .refills
+                    .iter()
+                    .flat_map(|refill| &refill.requirements),
+            )
+            .map(|group| (group, false, false))
+            .chain(anchor.connections.iter().map(|connection| {
+                (
+                    &connection.requirements,
+                    false,
+                    connection.kind == NodeKind::State,
+                )
+            }))
+            .collect(),
+    }
+}
+
+fn unknown_states_in_group<'a>(
+    group: &Group<'a>,
+    node_map: &FxHashMap<String, usize>,
+    unknown_states: &mut Vec<(&'a str, Range<usize>)>,
+) {
+    for line in &group.lines {
+        for requirement in line.ands.iter().chain(&line.ors) {
+            if let RequirementValue::State(state) = requirement.value {
+                if !node_map.contains_key(state) {
+                    unknown_states.push((state, requirement.range.clone()));
+                }
+            }
+        }
+        if let Some(group) = &line.group {
+            unknown_states_in_group(group, node_map, unknown_states);
+        }
+    }
+}
+
+fn used_macros_in_group<'a>(group: &Group<'a>, used_macros: &mut FxHashSet<&'a str>) {
+    for line in &group.lines {
+        for requirement in line.ands.iter().chain(&line.ors) {
+            if let RequirementValue::Macro(identifier) = requirement.value {
+                used_macros.insert(identifier);
+            }
+        }
+        if let Some(group) = &line.group {
+            used_macros_in_group(group, used_macros);
+        }
+    }
+}
+
+/// Reports lines that are impossible for all `world_settings` when combined with the lines they are nested in
+///
+/// Lines nested inside an impossible line won't be reported again
+///
+/// If `header_state` is set, lines that are explicitly `Impossible` are intended and won't be reported
+fn impossible_lines<'a>(
+    group: &Group<'a>,
+    region: bool,
+    header_state: bool,
+    outer: &Requirement,
+    context: &mut EmitterContext<'a>,
+    world_settings: &[WorldSettings],
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    for line in &group.lines {
+        if header_state && is_explicitly_impossible(line) {
+            continue;
+        }
+
+        let mut parts = vec![outer.clone()];
+        if !line.ands.is_empty() {
+            let ands = line
+                .ands
+                .iter()
+                .map(|and| build_requirement(and, region, context))
+                .collect();
+            parts.push(build_and(ands));
+        }
+        if !line.ors.is_empty() {
+            let ors = line
+                .ors
+                .iter()
+                .map(|or| build_requirement(or, region, context))
+                .collect();
+            parts.push(build_or(ors));
+        }
+        let requirement = build_and(parts);
+
+        if world_settings
+            .iter()
+            .any(|settings| requirement.is_possible_for(settings))
+        {
+            if let Some(group) = &line.group {
+                impossible_lines(
+                    group,
+                    region,
+                    header_state,
+                    &requirement,
+                    context,
+                    world_settings,
+                    diagnostics,
+                );
+            }
+        } else {
+            diagnostics.push(LintDiagnostic {
+                kind: LintKind::ImpossibleRequirement,
+                message: "This requirement can't be met on any difficulty".to_string(),
+                range: Some(line_range(line)),
+            });
+        }
+    }
+}
+
+fn is_explicitly_impossible(line: &Line) -> bool {
+    line.group.is_none()
+        && line
+            .ands
+            .iter()
+            .chain(&line.ors)
+            .any(|requirement| matches!(requirement.value, RequirementValue::Impossible))
+}
+
+fn line_range(line: &Line) -> Range<usize> {
+    let mut requirements = line.ands.iter().chain(&line.ors);
+    // The parser guarantees at least one requirement per line
+    let start = requirements.next().unwrap().range.clone();
+    let end = requirements.last().map_or(start.end, |last| last.range.end);
+    start.start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::LOCATIONS;
+    use crate::{files, logic};
+
+    const AREAS: &str = "
+requirement Unused:
+  moki: Bash
+
+requirement Used:
+  moki: DoubleJump
+
+anchor MarshSpawn.Main at 0, 0:
+  conn RoomA:
+    moki: Used
+    gorlek: impossible
+    kii:
+      unsafe: Launch
+      moki: Glide, impossible
+
+anchor RoomA at 10, 0:
+  state HeaderState:
+    moki: Impossible
+  pickup MarshSpawn.RockHC: free
+
+anchor Orphan at 20, 0:
+  conn MarshSpawn.Main: free
+";
+
+    #[test]
+    fn lint_diagnostics() {
+        let areas = Areas::parse(AREAS).unwrap();
+        let locations = logic::parse_locations(LOCATIONS).unwrap();
+        let diagnostics = lint(&areas, &locations);
+
+        let of_kind = |kind| {
+            diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.kind == kind)
+                .map(|diagnostic| {
+                    diagnostic
+                        .range
+                        .clone()
+                        .map_or(diagnostic.message.clone(), |range| AREAS[range].to_string())
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(of_kind(LintKind::UnreachableAnchor), ["Orphan"]);
+        assert_eq!(
+            of_kind(LintKind::UnusedLocation),
+            ["No anchor connects to pickup location MarshSpawn.FirstPickupEX"]
+        );
+        assert_eq!(of_kind(LintKind::UnusedRequirement), ["Unused"]);
+        assert_eq!(
+            of_kind(LintKind::ImpossibleRequirement),
+            ["impossible", "Glide, impossible"]
+        );
+        assert!(of_kind(LintKind::UnknownState).is_empty());
+
+        // Areas::parse rejects unknown states, but the recovered areas still contain them
+        let source = format!("{AREAS}\nanchor RoomB at 30, 0:\n  conn Orphan: MissingState\n");
+        let (areas, errors) = Areas::parse_recovering(&source);
+        assert_eq!(errors.len(), 1);
+        let unknown_states = lint(&areas, &locations)
+            .into_iter()
+            .filter(|diagnostic| diagnostic.kind == LintKind::UnknownState)
+            .map(|diagnostic| source[diagnostic.range.unwrap()].to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(unknown_states, ["MissingState"]);
+    }
+
+    #[test]
+    fn lint_logic() {
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let areas = Areas::parse(&areas).unwrap();
+        let locations = logic::parse_locations(&locations).unwrap();
+        let diagnostics = lint(&areas, &locations);
+        assert!(!diagnostics.iter().any(|diagnostic| matches!(
+            diagnostic.kind,
+            LintKind::UnusedRequirement | LintKind::ImpossibleRequirement
+        )));
+    }
+}
@@ -1,10 +1,15 @@
+pub mod analysis;
+mod diff;
 mod emitter;
+mod lint;
 mod locations;
 mod parser;
 mod states;
 mod tokenizer;
 
+pub use diff::{diff_logic, DifficultyDiff, LocationDiff, LogicDiff};
 pub use emitter::build;
+pub use lint::{lint, LintDiagnostic, LintKind};
 pub use locations::{parse_locations, Location};
 pub use parser::Areas;
 pub use states::{parse_states, NamedState};
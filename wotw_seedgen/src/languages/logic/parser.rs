@@ -38,10 +38,14 @@ pub enum AreaContent<'a> {
 pub struct NamedGroup<'a> {
     pub name: &'a str,
     pub group: Group<'a>,
+    /// Position of the name in the source
+    pub range: Range<usize>,
 }
 #[derive(Debug, Clone)]
 pub struct Anchor<'a> {
     pub identifier: &'a str,
+    /// Position of the identifier in the source
+    pub range: Range<usize>,
     pub position: Option<Position>,
     pub can_spawn: bool,
     pub teleport_restriction: Option<Group<'a>>,
@@ -57,7 +61,11 @@ pub struct Refill<'a> {
 pub struct Connection<'a> {
     pub kind: NodeKind,
     pub identifier: &'a str,
+    /// Position of the identifier in the source
+    pub range: Range<usize>,
     pub requirements: Group<'a>,
+    /// Whether this connection is a door that may be shuffled by door randomization
+    pub door: bool,
 }
 #[derive(Debug, Clone)]
 pub struct Group<'a> {
@@ -123,6 +131,18 @@ pub enum RequirementValue<'a> {
 impl<'a> Areas<'a> {
     /// Parses the input string into the [`Areas`] representation
     pub fn parse(input: &'a str) -> Result<Areas<'a>, ParseErrorCollection> {
+        let (areas, errors) = Self::parse_recovering(input);
+        match errors.is_empty() {
+            true => Ok(areas),
+            false => Err(errors),
+        }
+    }
+
+    /// Parses the input string into the [`Areas`] representation, continuing after errors
+    ///
+    /// Lines containing errors are skipped, the returned [`Areas`] contain everything else.
+    /// This is meant for tools that want to report all errors at once, use [`Areas::parse`] to build the logic.
+    pub fn parse_recovering(input: &'a str) -> (Areas<'a>, ParseErrorCollection) {
         let mut contents = Vec::new();
         let mut errors = ParseErrorCollection::default();
         let mut parser = new(input);
@@ -131,18 +151,18 @@ impl<'a> Areas<'a> {
             if parser.current_token().kind == TokenKind::Eof {
                 break;
             }
-            match parse_content(&mut parser) {
+            match parse_content(&mut parser, &mut errors) {
                 Ok(content) => contents.push(content),
-                Err(err) => errors.push(err),
+                Err(err) => {
+                    recover_line(&mut parser, &err);
+                    errors.push(err);
+                }
             }
         }
 
-        fill_macros_and_states(&mut contents, &parser).unwrap_or_else(|err| errors.push(err));
+        fill_macros_and_states(&mut contents, &parser, &mut errors);
 
-        match errors.is_empty() {
-            true => Ok(Self { contents }),
-            false => Err(errors),
-        }
+        (Self { contents }, errors)
     }
 }
 
@@ -166,26 +186,55 @@ enum Suggestion {
     Refill,
 }
 
-fn recover(parser: &mut Parser, recover_if: fn(TokenKind) -> bool) {
+/// Skips to the start of the next line on the same indentation level as the line that caused `err`
+///
+/// Lines indented further are skipped as well since they belong to the erroneous line
+fn recover_line(parser: &mut Parser, err: &ParseError) {
     let mut depth = 0;
+
+    let error_token = parser.read(err.range.clone());
+    if let Some(index) = error_token.rfind('\n') {
+        // The line break causing the error was consumed, so we are already past the end of the erroneous line
+        if parser.current_token().range != err.range {
+            let line_start = parser
+                .read(..err.range.start)
+                .rfind('\n')
+                .map_or(0, |index| index + 1);
+            let line_indent = indentation(parser.read(line_start..));
+            if indentation(&error_token[index + 1..]) <= line_indent {
+                return;
+            }
+            depth = 1;
+        }
+    }
+
     loop {
         let token = parser.current_token();
-        if token.kind == TokenKind::Eof {
-            break;
-        }
-        if token.kind == TokenKind::Indent {
-            depth += 1
-        }
-        if depth > 0 {
-            if matches!(token.kind, TokenKind::Dedent { .. }) {
-                depth -= 1
+        match token.kind {
+            TokenKind::Eof => break,
+            TokenKind::Indent => depth += 1,
+            TokenKind::Dedent { .. } => {
+                if depth == 0 {
+                    break;
+                }
+                depth -= 1;
+                if depth == 0 {
+                    parser.next_token();
+                    break;
+                }
             }
-        } else if recover_if(token.kind) {
-            break;
+            TokenKind::Newline if depth == 0 => {
+                parser.next_token();
+                break;
+            }
+            _ => {}
         }
         parser.next_token();
     }
 }
+fn indentation(line: &str) -> usize {
+    line.chars().take_while(|c| *c == ' ').count()
+}
 fn check_dedent(parser: &mut Parser) -> Result<bool, ParseError> {
     let token = parser.current_token();
     match token.kind {
@@ -207,26 +256,43 @@ enum ContentKind {
     Region,
     Anchor,
 }
-fn parse_content<'a>(parser: &mut Parser<'a>) -> Result<AreaContent<'a>, ParseError> {
+fn parse_content<'a>(
+    parser: &mut Parser<'a>,
+    errors: &mut ParseErrorCollection,
+) -> Result<AreaContent<'a>, ParseError> {
     let content_kind = parse_ident!(parser, Suggestion::Content)?;
     parser.eat_or_suggest(TokenKind::Whitespace, Suggestion::Content)?;
     match content_kind {
-        ContentKind::Requirement => parse_macro(parser),
-        ContentKind::Region => parse_region(parser),
-        ContentKind::Anchor => parse_anchor(parser),
+        ContentKind::Requirement => parse_macro(parser, errors),
+        ContentKind::Region => parse_region(parser, errors),
+        ContentKind::Anchor => parse_anchor(parser, errors),
     }
 }
-fn parse_macro<'a>(parser: &mut Parser<'a>) -> Result<AreaContent<'a>, ParseError> {
-    let name = read_ident!(parser, Suggestion::Identifier)?;
-    let group = parse_group(parser)?;
-    Ok(AreaContent::Requirement(NamedGroup { name, group }))
+fn parse_macro<'a>(
+    parser: &mut Parser<'a>,
+    errors: &mut ParseErrorCollection,
+) -> Result<AreaContent<'a>, ParseError> {
+    parse_named_group(parser, errors).map(AreaContent::Requirement)
 }
-fn parse_region<'a>(parser: &mut Parser<'a>) -> Result<AreaContent<'a>, ParseError> {
+fn parse_region<'a>(
+    parser: &mut Parser<'a>,
+    errors: &mut ParseErrorCollection,
+) -> Result<AreaContent<'a>, ParseError> {
+    parse_named_group(parser, errors).map(AreaContent::Region)
+}
+fn parse_named_group<'a>(
+    parser: &mut Parser<'a>,
+    errors: &mut ParseErrorCollection,
+) -> Result<NamedGroup<'a>, ParseError> {
+    let range = parser.current_token().range.clone();
     let name = read_ident!(parser, Suggestion::Identifier)?;
-    let group = parse_group(parser)?;
-    Ok(AreaContent::Region(NamedGroup { name, group }))
+    let group = parse_group(parser, errors)?;
+    Ok(NamedGroup { name, group, range })
 }
-fn parse_anchor<'a>(parser: &mut Parser<'a>) -> Result<AreaContent<'a>, ParseError> {
+fn parse_anchor<'a>(
+    parser: &mut Parser<'a>,
+    errors: &mut ParseErrorCollection,
+) -> Result<AreaContent<'a>, ParseError> {
     let token_range = parser.current_token().range.clone();
     let identifier = read_ident!(parser, Suggestion::Identifier)?;
     if identifier == "Random" || identifier == "FullyRandom" {
@@ -252,16 +318,14 @@ fn parse_anchor<'a>(parser: &mut Parser<'a>) -> Result<AreaContent<'a>, ParseErr
 
         let start = parser.current_token().range.start;
 
-        match parse_anchor_content(parser) {
+        match parse_anchor_content(parser, errors) {
             Ok(content) => match content {
                 AnchorContent::NoSpawn => can_spawn = false,
                 AnchorContent::TpRestriction(requirement) => {
                     if teleport_restriction.replace(requirement).is_some() {
                         let range = start..parser.current_token().range.start;
-                        recover(parser, |kind| matches!(kind, TokenKind::Dedent { .. }));
-                        check_dedent(parser)?;
-                        return Err(
-                            parser.error("An anchor may only have one teleport restriction", range)
+                        errors.push(
+                            parser.error("An anchor may only have one teleport restriction", range),
                         );
                     }
                 }
@@ -269,15 +333,18 @@ fn parse_anchor<'a>(parser: &mut Parser<'a>) -> Result<AreaContent<'a>, ParseErr
                 AnchorContent::Connection(connection) => connections.push(connection),
             },
             Err(err) => {
-                recover(parser, |kind| matches!(kind, TokenKind::Dedent { .. }));
-                check_dedent(parser)?;
-                return Err(err);
+                recover_line(parser, &err);
+                errors.push(err);
+                if parser.current_token().kind == TokenKind::Eof {
+                    break;
+                }
             }
         }
     }
 
     Ok(AreaContent::Anchor(Anchor {
         identifier,
+        range: token_range,
         position,
         can_spawn,
         teleport_restriction,
@@ -312,6 +379,7 @@ enum AnchorContentKind {
     Quest,
     Pickup,
     Conn,
+    Door,
 }
 enum AnchorContent<'a> {
     NoSpawn,
@@ -319,7 +387,10 @@ enum AnchorContent<'a> {
     Refill(Refill<'a>),
     Connection(Connection<'a>),
 }
-fn parse_anchor_content<'a>(parser: &mut Parser<'a>) -> Result<AnchorContent<'a>, ParseError> {
+fn parse_anchor_content<'a>(
+    parser: &mut Parser<'a>,
+    errors: &mut ParseErrorCollection,
+) -> Result<AnchorContent<'a>, ParseError> {
     let kind = parse_ident!(parser, Suggestion::AnchorContent)?;
     let content = match kind {
         AnchorContentKind::NoSpawn => {
@@ -327,26 +398,36 @@ fn parse_anchor_content<'a>(parser: &mut Parser<'a>) -> Result<AnchorContent<'a>
             parser.eat_or_suggest(TokenKind::Newline, Suggestion::AnchorContent)?;
             AnchorContent::NoSpawn
         }
-        AnchorContentKind::TpRestriction => AnchorContent::TpRestriction(parse_group(parser)?),
-        AnchorContentKind::Refill => AnchorContent::Refill(parse_anchor_refill(parser)?),
+        AnchorContentKind::TpRestriction => {
+            AnchorContent::TpRestriction(parse_group(parser, errors)?)
+        }
+        AnchorContentKind::Refill => AnchorContent::Refill(parse_anchor_refill(parser, errors)?),
         AnchorContentKind::State => {
-            AnchorContent::Connection(parse_anchor_connection(parser, NodeKind::State)?)
+            AnchorContent::Connection(parse_anchor_connection(parser, NodeKind::State, errors)?)
         }
         AnchorContentKind::Quest => {
-            AnchorContent::Connection(parse_anchor_connection(parser, NodeKind::Quest)?)
+            AnchorContent::Connection(parse_anchor_connection(parser, NodeKind::Quest, errors)?)
         }
         AnchorContentKind::Pickup => {
-            AnchorContent::Connection(parse_anchor_connection(parser, NodeKind::Pickup)?)
+            AnchorContent::Connection(parse_anchor_connection(parser, NodeKind::Pickup, errors)?)
         }
         AnchorContentKind::Conn => {
-            AnchorContent::Connection(parse_anchor_connection(parser, NodeKind::Anchor)?)
+            AnchorContent::Connection(parse_anchor_connection(parser, NodeKind::Anchor, errors)?)
+        }
+        AnchorContentKind::Door => {
+            let mut connection = parse_anchor_connection(parser, NodeKind::Anchor, errors)?;
+            connection.door = true;
+            AnchorContent::Connection(connection)
         }
     };
     Ok(content)
 }
-fn parse_optional_group<'a>(parser: &mut Parser<'a>) -> Result<Option<Group<'a>>, ParseError> {
+fn parse_optional_group<'a>(
+    parser: &mut Parser<'a>,
+    errors: &mut ParseErrorCollection,
+) -> Result<Option<Group<'a>>, ParseError> {
     let group = match parser.current_token().kind {
-        TokenKind::Colon => Some(parse_group(parser)?),
+        TokenKind::Colon => Some(parse_group(parser, errors)?),
         _ => {
             parser.skip(TokenKind::Whitespace);
             parser.eat_or_suggest(TokenKind::Newline, Suggestion::Refill)?;
@@ -364,10 +445,13 @@ enum RefillKind {
     Health,
     Energy,
 }
-fn parse_anchor_refill<'a>(parser: &mut Parser<'a>) -> Result<Refill<'a>, ParseError> {
+fn parse_anchor_refill<'a>(
+    parser: &mut Parser<'a>,
+    errors: &mut ParseErrorCollection,
+) -> Result<Refill<'a>, ParseError> {
     parser.eat_or_suggest(TokenKind::Whitespace, Suggestion::AnchorContent)?;
     let value = parse_refill_value(parser)?;
-    let requirements = parse_optional_group(parser)?;
+    let requirements = parse_optional_group(parser, errors)?;
 
     Ok(Refill {
         value,
@@ -390,18 +474,28 @@ fn parse_refill_value(parser: &mut Parser) -> Result<RefillValue, ParseError> {
 fn parse_anchor_connection<'a>(
     parser: &mut Parser<'a>,
     kind: NodeKind,
+    errors: &mut ParseErrorCollection,
 ) -> Result<Connection<'a>, ParseError> {
     parser.eat_or_suggest(TokenKind::Whitespace, Suggestion::AnchorContent)?;
+    let range = parser.current_token().range.clone();
     let identifier = read_ident!(parser, Suggestion::Identifier)?;
-    let requirements = parse_group(parser)?;
+    let requirements = parse_group(parser, errors)?;
     Ok(Connection {
         kind,
         identifier,
+        range,
         requirements,
+        door: false,
     })
 }
 
-fn parse_group<'a>(parser: &mut Parser<'a>) -> Result<Group<'a>, ParseError> {
+/// Parses a group of requirement lines
+///
+/// Errors in the lines of a multiline group are added to `errors` and the erroneous lines are skipped
+fn parse_group<'a>(
+    parser: &mut Parser<'a>,
+    errors: &mut ParseErrorCollection,
+) -> Result<Group<'a>, ParseError> {
     parser.eat(TokenKind::Colon)?;
     parser.skip(TokenKind::Whitespace);
 
@@ -410,29 +504,32 @@ fn parse_group<'a>(parser: &mut Parser<'a>) -> Result<Group<'a>, ParseError> {
         parser.next_token();
 
         loop {
-            match parse_line(parser) {
-                Ok(line) => {
-                    lines.push(line);
-                    if check_dedent(parser)? {
+            match parse_line(parser, errors) {
+                Ok(line) => lines.push(line),
+                Err(err) => {
+                    recover_line(parser, &err);
+                    errors.push(err);
+                    if parser.current_token().kind == TokenKind::Eof {
                         break;
                     }
                 }
-                Err(err) => {
-                    recover(parser, |kind| matches!(kind, TokenKind::Dedent { .. }));
-                    check_dedent(parser)?;
-                    return Err(err);
-                }
+            }
+            if check_dedent(parser)? {
+                break;
             }
         }
     } else {
-        let line = parse_line(parser)?;
+        let line = parse_line(parser, errors)?;
         lines.push(line);
     }
 
     Ok(Group { lines })
 }
 
-fn parse_line<'a>(parser: &mut Parser<'a>) -> Result<Line<'a>, ParseError> {
+fn parse_line<'a>(
+    parser: &mut Parser<'a>,
+    errors: &mut ParseErrorCollection,
+) -> Result<Line<'a>, ParseError> {
     let mut ands = Vec::new();
     let mut ors = Vec::with_capacity(1);
     let mut has_seen_or = false;
@@ -457,7 +554,7 @@ fn parse_line<'a>(parser: &mut Parser<'a>) -> Result<Line<'a>, ParseError> {
                 break None;
             }
             TokenKind::Colon => {
-                let group = parse_group(parser)?;
+                let group = parse_group(parser, errors)?;
                 ors.push(requirement);
                 break Some(group);
             }
@@ -548,6 +645,32 @@ pub enum Skill {
     Sentry = 116,
     Flap = 118,
 }
+impl Skill {
+    pub(super) const ALL: [Skill; 22] = [
+        Skill::Bash,
+        Skill::WallJump,
+        Skill::DoubleJump,
+        Skill::Launch,
+        Skill::Glide,
+        Skill::WaterBreath,
+        Skill::Grenade,
+        Skill::Grapple,
+        Skill::Flash,
+        Skill::Spear,
+        Skill::Regenerate,
+        Skill::Bow,
+        Skill::Hammer,
+        Skill::Sword,
+        Skill::Burrow,
+        Skill::Dash,
+        Skill::WaterDash,
+        Skill::Shuriken,
+        Skill::Seir,
+        Skill::Blaze,
+        Skill::Sentry,
+        Skill::Flap,
+    ];
+}
 impl From<Skill> for item::Skill {
     fn from(skill: Skill) -> item::Skill {
         item::Skill::try_from(skill as u8).unwrap()
@@ -720,7 +843,8 @@ fn parse_combat_requirement<'a>(
 fn fill_macros_and_states(
     contents: &mut Vec<AreaContent>,
     parser: &Parser,
-) -> Result<(), ParseError> {
+    errors: &mut ParseErrorCollection,
+) {
     let mut macros = Vec::new();
     let mut states = Vec::new();
     for content in contents.iter_mut() {
@@ -740,56 +864,60 @@ fn fill_macros_and_states(
     for content in contents {
         match content {
             AreaContent::Requirement(named_group) | AreaContent::Region(named_group) => {
-                fill_group(&mut named_group.group, &macros, &states, parser)?
+                fill_group(&mut named_group.group, &macros, &states, parser, errors)
             }
             AreaContent::Anchor(anchor) => {
                 for refill in &mut anchor.refills {
                     for requirement in &mut refill.requirements {
-                        fill_group(requirement, &macros, &states, parser)?;
+                        fill_group(requirement, &macros, &states, parser, errors);
                     }
                 }
                 for connection in &mut anchor.connections {
-                    fill_group(&mut connection.requirements, &macros, &states, parser)?;
+                    fill_group(
+                        &mut connection.requirements,
+                        &macros,
+                        &states,
+                        parser,
+                        errors,
+                    );
                 }
             }
         }
     }
-
-    Ok(())
 }
 fn fill_group(
     group: &mut Group,
     macros: &[&str],
     states: &[&str],
     parser: &Parser,
-) -> Result<(), ParseError> {
+    errors: &mut ParseErrorCollection,
+) {
     for line in &mut group.lines {
         for and in &mut line.ands {
-            fill_requirement(and, macros, states, parser)?;
+            fill_requirement(and, macros, states, parser, errors);
         }
         for or in &mut line.ors {
-            fill_requirement(or, macros, states, parser)?;
+            fill_requirement(or, macros, states, parser, errors);
         }
         for group in &mut line.group {
-            fill_group(group, macros, states, parser)?;
+            fill_group(group, macros, states, parser, errors);
         }
     }
-    Ok(())
 }
 fn fill_requirement(
     requirement: &mut Requirement,
     macros: &[&str],
     states: &[&str],
     parser: &Parser,
-) -> Result<(), ParseError> {
+    errors: &mut ParseErrorCollection,
+) {
     if let RequirementValue::State(identifier) = requirement.value {
         if macros.contains(&identifier) {
             requirement.value = RequirementValue::Macro(identifier);
         } else if !states.contains(&identifier) {
-            return Err(parser.error("unknown requirement", requirement.range.clone()));
+            errors.push(parser.error("unknown requirement", requirement.range.clone()));
         };
     }
-    Ok(())
 }
 
 #[cfg(test)]
@@ -1,3 +1,4 @@
+pub mod analysis;
 mod cursor;
 pub mod header;
 pub mod logic;
@@ -5,5 +6,5 @@ use cursor::Cursor;
 mod token;
 pub(crate) use token::{CommentKind, Token, TokenKind};
 mod parser;
-pub use parser::ParseError;
 pub(crate) use parser::Parser;
+pub use parser::{ParseError, ParseErrorCollection};
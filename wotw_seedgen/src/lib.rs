@@ -18,9 +18,13 @@ mod reach_check;
 pub mod settings;
 pub mod uber_state;
 pub mod util;
+pub mod verify;
 pub mod world;
 
-pub use generator::generate_seed;
+#[cfg(test)]
+mod test_fixtures;
+
+pub use generator::{generate_seed, generate_seed_observed};
 pub use inventory::Inventory;
 pub use item::{Item, VItem};
 pub use languages::{
@@ -28,6 +32,7 @@ pub use languages::{
     logic,
 };
 pub use reach_check::reach_check;
+pub use verify::verify_seed;
 pub use world::World;
 
 pub const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "-", env!("VERGEN_GIT_SHA"));
@@ -7,7 +7,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     files::FileAccess,
-    settings::{CreateGame, Difficulty, GoalModes, HeaderConfig, InlineHeader, Spawn, Trick},
+    settings::{
+        CreateGame, Difficulty, GenerationTuning, GoalModes, HeaderConfig, HintSettings,
+        InlineHeader, ItemPoolSettings, PlacementConstraint, Spawn, Trick,
+    },
 };
 
 /// A collection of settings that can be applied to existing settings
@@ -75,6 +78,17 @@ pub struct UniversePreset {
     /// This exists for future compability, but does not have any effect currently
     #[serde(skip_serializing_if = "Option::is_none")]
     pub create_game: Option<CreateGame>,
+    /// Values that shape the generation process
+    ///
+    /// Values left out of the preset will be reset to their defaults
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tuning: Option<GenerationTuning>,
+    /// Determine which placements are required to finish each world and add them to the spoiler
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_items: Option<bool>,
+    /// Shuffle the connections marked as doors in the logic
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub door_randomization: Option<bool>,
 }
 
 impl UniversePreset {
@@ -176,6 +190,15 @@ pub struct WorldPreset {
     /// Inline header syntax
     #[serde(skip_serializing_if = "Option::is_none")]
     pub inline_headers: Option<Vec<InlineHeader>>,
+    /// Restrictions on where specific items may be placed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placement_constraints: Option<Vec<PlacementConstraint>>,
+    /// Which hints to generate from the final placements
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hints: Option<HintSettings>,
+    /// Which items will be placed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_pool: Option<ItemPoolSettings>,
 }
 
 impl WorldPreset {
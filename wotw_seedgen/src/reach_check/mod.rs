@@ -53,7 +53,7 @@ fn set_node(world: &mut World, identifier: &str) {
     }
 }
 
-fn apply_sets(world: &mut World, seed_file: &str) -> Result<(), String> {
+pub(crate) fn apply_sets(world: &mut World, seed_file: &str) -> Result<(), String> {
     for line in seed_file.lines() {
         if let Some(sets) = line.strip_prefix("// Sets: ") {
             if !sets.is_empty() {
@@ -10,16 +10,22 @@ use std::{collections::hash_map::DefaultHasher, error::Error, fmt, hash::Hasher,
 
 use rand::distributions::{Distribution, Uniform};
 use rustc_hash::FxHashSet;
-use serde::de::Visitor;
+use serde::de::{MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use smallvec::{smallvec, SmallVec};
 use wotw_seedgen_derive::{Display, FromStr};
 
-use crate::item::Skill;
+use crate::item::{Item, Skill};
 use crate::{
     files::FileAccess,
     preset::{UniversePreset, WorldPreset},
-    util::constants::DEFAULT_SPAWN,
+    uber_state::{UberIdentifier, UberStateComparator, UberStateTrigger},
+    util::constants::{
+        DEFAULT_SPAWN, PLACEHOLDER_SLOTS, PREFERRED_SPAWN_SLOTS, RANDOM_PROGRESSION, RESERVE_SLOTS,
+        RETRIES, SPAWN_SLOTS,
+    },
+    util::{NodeKind, Zone},
+    world::graph::Node,
 };
 
 use slugstrings::SLUGSTRINGS;
@@ -86,6 +92,24 @@ pub struct UniverseSettings {
     ///
     /// This exists for future compability, but does not have any effect currently
     pub create_game: CreateGame,
+    /// Values that shape the generation process
+    ///
+    /// The defaults are tried and tested, these are intended for experimentation
+    #[serde(default)]
+    pub tuning: GenerationTuning,
+    /// Determine which placements are required to finish each world and add them to the spoiler
+    ///
+    /// This replays the seed many times and slows down generation noticeably
+    #[serde(default)]
+    pub required_items: bool,
+    /// Shuffle the connections marked as doors in the logic
+    ///
+    /// The door layout is decided per seed and shared by all worlds
+    ///
+    /// The randomizer client can't apply door layouts yet, so this is only allowed with [`GenerationTuning::experimental_doors`].
+    /// The layout is part of the spoiler and a comment in the seed files
+    #[serde(default)]
+    pub door_randomization: bool,
 }
 
 impl UniverseSettings {
@@ -144,6 +168,9 @@ impl UniverseSettings {
             online,
             seed,
             create_game,
+            tuning,
+            required_items,
+            door_randomization,
         } = preset;
 
         if let Some(includes) = includes {
@@ -197,6 +224,15 @@ impl UniverseSettings {
         if let Some(create_game) = create_game {
             self.create_game = create_game;
         }
+        if let Some(tuning) = tuning {
+            self.tuning = tuning;
+        }
+        if let Some(required_items) = required_items {
+            self.required_items = required_items;
+        }
+        if let Some(door_randomization) = door_randomization {
+            self.door_randomization = door_randomization;
+        }
 
         Ok(())
     }
@@ -326,6 +362,9 @@ impl Default for UniverseSettings {
             disable_logic_filter: false,
             online: false,
             create_game: CreateGame::default(),
+            tuning: GenerationTuning::default(),
+            required_items: false,
+            door_randomization: false,
         }
     }
 }
@@ -366,6 +405,15 @@ pub struct WorldSettings {
     pub header_config: Vec<HeaderConfig>,
     /// Fully qualified header syntax
     pub inline_headers: Vec<InlineHeader>,
+    /// Restrictions on where specific items may be placed
+    #[serde(default)]
+    pub placement_constraints: Vec<PlacementConstraint>,
+    /// Which hints to generate from the final placements
+    #[serde(default)]
+    pub hints: HintSettings,
+    /// Which items will be placed
+    #[serde(default)]
+    pub item_pool: ItemPoolSettings,
 }
 
 impl WorldSettings {
@@ -410,7 +458,15 @@ impl WorldSettings {
 
     /// Checks whether these settings feature a random spawn location
     pub fn is_random_spawn(&self) -> bool {
-        matches!(self.spawn, Spawn::Random | Spawn::FullyRandom)
+        !matches!(self.spawn, Spawn::Set(_))
+    }
+
+    /// Checks whether the [`PlacementConstraint`]s permit placing the [`Item`] on the [`Node`]
+    pub fn allows_placement(&self, item: &Item, node: &Node) -> bool {
+        self.placement_constraints
+            .iter()
+            .filter(|constraint| &constraint.item == item)
+            .all(|constraint| constraint.allows(node))
     }
 
     /// Apply the settings from a [`WorldPreset`]
@@ -446,6 +502,9 @@ impl WorldSettings {
             headers,
             header_config,
             inline_headers,
+            placement_constraints,
+            hints,
+            item_pool,
         } = preset;
 
         if let Some(includes) = includes {
@@ -480,6 +539,16 @@ impl WorldSettings {
         if let Some(mut inline_headers) = inline_headers {
             self.inline_headers.append(&mut inline_headers);
         }
+        if let Some(mut placement_constraints) = placement_constraints {
+            self.placement_constraints
+                .append(&mut placement_constraints);
+        }
+        if let Some(hints) = hints {
+            self.hints = hints;
+        }
+        if let Some(item_pool) = item_pool {
+            self.item_pool = item_pool;
+        }
 
         Ok(())
     }
@@ -502,6 +571,8 @@ impl WorldSettings {
 }
 
 /// The Spawn destination, determining the starting location of the seed
+///
+/// Spawning anywhere other than [`DEFAULT_SPAWN`] usually needs the `better_random_spawn` header to find early progression
 #[derive(Debug, Clone, PartialEq)]
 pub enum Spawn {
     /// Spawn in a specific location, described by the anchor name from the logic file
@@ -510,6 +581,10 @@ pub enum Spawn {
     Random,
     /// Spawn on any valid anchor from the logic file
     FullyRandom,
+    /// Spawn in a random location out of the given anchor names, each being equally likely
+    OneOf(Vec<String>),
+    /// Spawn in a random location out of the given anchor names, with the likelihood of each being proportional to its weight
+    Weighted(Vec<(String, u32)>),
 }
 
 impl Default for Spawn {
@@ -527,17 +602,23 @@ impl Serialize for Spawn {
             Spawn::Random => serializer.serialize_str("Random"),
             Spawn::FullyRandom => serializer.serialize_str("FullyRandom"),
             Spawn::Set(spawn_loc) => serializer.serialize_str(spawn_loc),
+            Spawn::OneOf(spawn_locs) => serializer.collect_seq(spawn_locs),
+            Spawn::Weighted(spawn_locs) => serializer.collect_map(
+                spawn_locs
+                    .iter()
+                    .map(|(spawn_loc, weight)| (spawn_loc, weight)),
+            ),
         }
     }
 }
 
-struct SpawnStringVisitor;
+struct SpawnVisitor;
 
-impl<'de> Visitor<'de> for SpawnStringVisitor {
+impl<'de> Visitor<'de> for SpawnVisitor {
     type Value = Spawn;
 
     fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
-        formatter.write_str("a string representing an anchor, 'Random' or 'FullyRandom'")
+        formatter.write_str("a string representing an anchor, 'Random' or 'FullyRandom', a list of anchors or a map of anchors to weights")
     }
 
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
@@ -550,6 +631,28 @@ impl<'de> Visitor<'de> for SpawnStringVisitor {
             set => Ok(Spawn::Set(set.to_string())),
         }
     }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut spawn_locs = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(spawn_loc) = seq.next_element()? {
+            spawn_locs.push(spawn_loc);
+        }
+        Ok(Spawn::OneOf(spawn_locs))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut spawn_locs = Vec::with_capacity(map.size_hint().unwrap_or_default());
+        while let Some(entry) = map.next_entry()? {
+            spawn_locs.push(entry);
+        }
+        Ok(Spawn::Weighted(spawn_locs))
+    }
 }
 
 impl<'de> Deserialize<'de> for Spawn {
@@ -557,7 +660,7 @@ impl<'de> Deserialize<'de> for Spawn {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_str(SpawnStringVisitor)
+        deserializer.deserialize_any(SpawnVisitor)
     }
 }
 
@@ -722,6 +825,33 @@ pub enum Trick {
     /// Storing a grounded jump into the air with Spear
     SpearJump,
 }
+impl Trick {
+    /// Every available [`Trick`]
+    pub const ALL: [Trick; 22] = [
+        Trick::SwordSentryJump,
+        Trick::HammerSentryJump,
+        Trick::ShurikenBreak,
+        Trick::SentryBreak,
+        Trick::HammerBreak,
+        Trick::SpearBreak,
+        Trick::SentryBurn,
+        Trick::RemoveKillPlane,
+        Trick::LaunchSwap,
+        Trick::SentrySwap,
+        Trick::FlashSwap,
+        Trick::BlazeSwap,
+        Trick::WaveDash,
+        Trick::GrenadeJump,
+        Trick::HammerJump,
+        Trick::SwordJump,
+        Trick::GrenadeRedirect,
+        Trick::SentryRedirect,
+        Trick::PauseHover,
+        Trick::GlideJump,
+        Trick::GlideHammerJump,
+        Trick::SpearJump,
+    ];
+}
 
 /// Enforced Requirement before being allowed to finish the game
 ///
@@ -744,6 +874,8 @@ pub enum Goal {
     /// Each zone of the game will have at most one Relic
     /// There are 11 zones that allow Relics, the specified chance represents how likely each single zone will have a relic
     RelicChance(f64),
+    /// Require a set of uberState conditions defined by a header before finishing the game
+    Custom(CustomGoal),
 }
 impl Display for Goal {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -753,6 +885,7 @@ impl Display for Goal {
             Goal::Quests => "Quests".fmt(f),
             Goal::Relics(count) => write!(f, "{} Relics", count),
             Goal::RelicChance(chance) => write!(f, "{}% Relic chance", chance * 100.),
+            Goal::Custom(goal) => goal.flag.fmt(f),
         }
     }
 }
@@ -760,12 +893,13 @@ impl Goal {
     /// Returns the flag name representing this goal
     ///
     /// The flag name communicates to the randomizer client which restrictions to apply before allowing to finish the game
-    pub fn flag_name(&self) -> &'static str {
+    pub fn flag_name(&self) -> &str {
         match self {
             Goal::Wisps => "All Wisps",
             Goal::Trees => "All Trees",
             Goal::Quests => "All Quests",
             Goal::Relics(_) | Goal::RelicChance(_) => "Relics",
+            Goal::Custom(goal) => &goal.flag,
         }
     }
 
@@ -774,6 +908,32 @@ impl Goal {
     }
 }
 
+/// A [`Goal`] defined through uberState conditions, usually declared by a header
+///
+/// Generated seeds set the uberState `16|n` once the conditions of the n-th custom goal are met
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomGoal {
+    /// The flag name communicated to the randomizer client
+    pub flag: String,
+    /// Conditions that all have to be met before finishing the game
+    pub conditions: Vec<UberStateTrigger>,
+}
+impl CustomGoal {
+    /// Checks whether all conditions are met by the uberState values
+    ///
+    /// Conditions without a comparison are met once their uberState is set
+    pub fn is_met(&self, mut get_uber_state: impl FnMut(UberIdentifier) -> f32) -> bool {
+        self.conditions.iter().all(|trigger| {
+            let value = get_uber_state(trigger.identifier);
+            trigger
+                .condition
+                .as_ref()
+                .map_or(value > 0., |condition| condition.met_by(value))
+        })
+    }
+}
+
 /// A collection of non-redundant [`Goal`]s
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -781,7 +941,7 @@ pub struct GoalModes {
     goals: Vec<Goal>,
 }
 impl GoalModes {
-    /// Adds a [`Goal`], ensuring that it isn't redundant with existing [`Goal`]s
+    /// Adds a [`Goal`], ensuring that it isn't redundant with or contradicting existing [`Goal`]s
     pub fn add(&mut self, goal: Goal) -> Result<(), String> {
         if self.goals.contains(&goal) {
             return Ok(());
@@ -793,10 +953,68 @@ impl GoalModes {
             }
         }
 
+        if let Goal::Custom(custom) = &goal {
+            if custom.flag.is_empty() {
+                return Err("Custom goals need a flag name".to_string());
+            }
+            if custom.conditions.is_empty() {
+                return Err(format!(
+                    "Custom goal {} needs at least one condition",
+                    custom.flag
+                ));
+            }
+            if [Goal::Wisps, Goal::Trees, Goal::Quests, Goal::Relics(0)]
+                .iter()
+                .any(|built_in| built_in.flag_name() == custom.flag)
+            {
+                return Err(format!(
+                    "Custom goal {} uses the flag of a built-in goal",
+                    custom.flag
+                ));
+            }
+            if let Some(other) = self
+                .goals
+                .iter()
+                .find(|other| other.flag_name() == custom.flag)
+            {
+                return Err(format!(
+                    "Contradicting goal modes {} and {}: both use the flag {}",
+                    other, goal, custom.flag
+                ));
+            }
+
+            for condition in &custom.conditions {
+                let (min, max) = self
+                    .goals
+                    .iter()
+                    .filter_map(|goal| match goal {
+                        Goal::Custom(goal) => Some(&goal.conditions),
+                        _ => None,
+                    })
+                    .flatten()
+                    .chain(&custom.conditions)
+                    .filter(|other| other.identifier == condition.identifier)
+                    .map(condition_bounds)
+                    .fold(
+                        (i64::MIN, i64::MAX),
+                        |(min, max), (other_min, other_max)| {
+                            (min.max(other_min), max.min(other_max))
+                        },
+                    );
+                if min > max {
+                    return Err(format!(
+                        "Contradicting goal conditions on {}",
+                        condition.identifier
+                    ));
+                }
+            }
+        }
+
         self.goals.push(goal);
         Ok(())
     }
 }
+
 impl FromIterator<Goal> for GoalModes {
     fn from_iter<T: IntoIterator<Item = Goal>>(iter: T) -> Self {
         Self {
@@ -823,6 +1041,23 @@ impl DerefMut for GoalModes {
     }
 }
 
+/// The smallest and largest uberState values that meet the condition
+fn condition_bounds(trigger: &UberStateTrigger) -> (i64, i64) {
+    let condition = match &trigger.condition {
+        Some(condition) => condition,
+        None => return (i64::MIN, i64::MAX),
+    };
+    let value = i64::from(condition.value);
+
+    match condition.comparator {
+        UberStateComparator::Equals => (value, value),
+        UberStateComparator::Greater => (value + 1, i64::MAX),
+        UberStateComparator::GreaterOrEquals => (value, i64::MAX),
+        UberStateComparator::Less => (i64::MIN, value - 1),
+        UberStateComparator::LessOrEquals => (i64::MIN, value),
+    }
+}
+
 /// Different types of online games that can be automatically created when generating the seed
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromStr)]
 #[ParseFromIdentifier]
@@ -845,6 +1080,236 @@ impl Default for CreateGame {
     }
 }
 
+/// Restricts which locations an [`Item`] may be placed on
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::PlacementConstraint;
+/// use wotw_seedgen::settings::{ConstraintMode, LocationFilter};
+/// use wotw_seedgen::util::Zone;
+///
+/// let json = "{\"item\":\"2|8\",\"mode\":\"Only\",\"locations\":[{\"zone\":\"Marsh\"}]}";
+/// let constraint: PlacementConstraint = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(constraint.mode, ConstraintMode::Only);
+/// assert_eq!(constraint.locations, vec![LocationFilter::Zone(Zone::Marsh)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PlacementConstraint {
+    /// The constrained [`Item`]
+    pub item: Item,
+    /// How to interpret the `locations`
+    pub mode: ConstraintMode,
+    /// The locations this constraint refers to
+    pub locations: Vec<LocationFilter>,
+}
+
+impl PlacementConstraint {
+    /// Checks whether this constraint permits placing its [`Item`] on the [`Node`]
+    pub fn allows(&self, node: &Node) -> bool {
+        let matches = self.locations.iter().any(|location| location.matches(node));
+        match self.mode {
+            ConstraintMode::Only => matches,
+            ConstraintMode::Never => !matches,
+        }
+    }
+}
+
+/// How a [`PlacementConstraint`] treats its locations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConstraintMode {
+    /// The [`Item`] has to be placed on one of the locations
+    Only,
+    /// The [`Item`] may not be placed on any of the locations
+    Never,
+}
+
+/// A set of locations used in a [`PlacementConstraint`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LocationFilter {
+    /// Any location in the [`Zone`]
+    ///
+    /// Most shop items are in [`Zone::Shop`], but Lupo's maps belong to the zone they show
+    Zone(Zone),
+    /// Any location sold in a shop, including Lupo's maps
+    Shop,
+    /// The location with this identifier from the logic file
+    Node(String),
+    /// Any location of this [`NodeKind`]
+    Kind(NodeKind),
+}
+
+impl LocationFilter {
+    /// Checks whether the [`Node`] is part of this set of locations
+    pub fn matches(&self, node: &Node) -> bool {
+        match self {
+            LocationFilter::Zone(zone) => node.zone() == Some(*zone),
+            LocationFilter::Shop => node
+                .trigger()
+                .is_some_and(|trigger| trigger.identifier.is_purchasable()),
+            LocationFilter::Node(identifier) => node.identifier() == identifier,
+            LocationFilter::Kind(kind) => node.node_kind() == *kind,
+        }
+    }
+}
+
+/// Hint policy for a world
+///
+/// The hints are generated after all items have been placed and shown as messages on the `triggers`
+///
+/// Any values that are left out when deserializing will use their defaults, which generate no hints
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct HintSettings {
+    /// How many zones containing items required to finish the seed should be named
+    ///
+    /// This needs [`UniverseSettings::required_items`] to be enabled
+    pub way_of_the_hero: usize,
+    /// How many zones containing no required items should be named
+    ///
+    /// This needs [`UniverseSettings::required_items`] to be enabled
+    pub barren: usize,
+    /// Whether to hint the amount of skills and teleporters in every zone
+    pub zone_counts: bool,
+    /// Pickup triggers that show the hints, such as `48248|18767`
+    ///
+    /// The hints are spread evenly across the triggers. If no triggers are provided, all hints will be shown on spawn
+    pub triggers: Vec<String>,
+}
+
+impl HintSettings {
+    /// Checks whether any hints should be generated
+    pub fn any(&self) -> bool {
+        self.way_of_the_hero > 0 || self.barren > 0 || self.zone_counts
+    }
+}
+
+/// The items that should be placed in a world
+///
+/// Headers may still add and remove items on top of this
+///
+/// Any values that are left out when deserializing will use their defaults, which match the standard item pool
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct ItemPoolSettings {
+    /// The pool to start from
+    pub base: BasePool,
+    /// Items that should be placed a different amount of times than in the `base` pool
+    ///
+    /// Later entries for the same [`Item`] override earlier ones, a `count` of 0 removes the [`Item`] from the pool
+    pub items: Vec<ItemCount>,
+    /// The total amount of Spirit Light, if different from the `base` pool
+    pub spirit_light: Option<u32>,
+    /// What to place on the locations left over after placing the entire pool
+    pub junk: JunkFill,
+}
+/// A starting point for the [`ItemPoolSettings`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BasePool {
+    /// The standard item pool with every Skill, Shard, Teleporter and a set amount of resources
+    #[default]
+    Standard,
+    /// No items and no Spirit Light
+    Empty,
+}
+/// How often an [`Item`] should be placed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ItemCount {
+    pub item: Item,
+    pub count: u32,
+}
+/// What to place on the locations left over after placing the entire pool
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JunkFill {
+    /// Split up the pool's Spirit Light between the remaining locations
+    #[default]
+    SpiritLight,
+    /// Fill the remaining locations with copies of an [`Item`]
+    ///
+    /// Enough locations will be left free for the pool's Spirit Light to average out at 100 per location
+    Item(Item),
+}
+
+/// Values that shape the generation process for every world
+///
+/// Any values that are left out when deserializing will use their defaults
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct GenerationTuning {
+    /// How many attempts to allow when generating a seed
+    pub retries: u16,
+    /// How likely to choose a progression item as random placement
+    pub random_progression: f64,
+    /// How many slots to keep as placeholders for bigger progressions
+    pub placeholder_slots: usize,
+    /// How many slots to reserve after random placements for the next iteration
+    pub reserve_slots: usize,
+    /// How many items may be placed on spawn when not spawning in the default location
+    pub spawn_slots: usize,
+    /// How many of the `spawn_slots` may be used without reducing the weight of a progression
+    pub preferred_spawn_slots: usize,
+    /// Range of factors to randomly multiply shop prices with
+    pub shop_price_range: (f32, f32),
+    /// Run the attempts to generate a seed in parallel
+    ///
+    /// Each attempt will use its own rng derived from the seed, so the result differs from sequential generation,
+    /// but it remains reproducible regardless of the number of threads
+    pub parallel_attempts: bool,
+    /// Allow [`UniverseSettings::door_randomization`]
+    ///
+    /// The randomizer client can't apply door layouts yet, so seeds generated with shuffled doors are not playable
+    pub experimental_doors: bool,
+}
+
+impl Default for GenerationTuning {
+    fn default() -> GenerationTuning {
+        GenerationTuning {
+            retries: RETRIES,
+            random_progression: RANDOM_PROGRESSION,
+            placeholder_slots: PLACEHOLDER_SLOTS,
+            reserve_slots: RESERVE_SLOTS,
+            spawn_slots: SPAWN_SLOTS,
+            preferred_spawn_slots: PREFERRED_SPAWN_SLOTS,
+            shop_price_range: (0.75, 1.25),
+            parallel_attempts: false,
+            experimental_doors: false,
+        }
+    }
+}
+
+impl GenerationTuning {
+    /// Checks whether the values can be used to generate a seed
+    pub fn validate(&self) -> Result<(), String> {
+        if self.retries == 0 {
+            return Err("retries has to be at least 1".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.random_progression) {
+            return Err(format!(
+                "randomProgression has to be between 0 and 1, got {}",
+                self.random_progression
+            ));
+        }
+        if self.preferred_spawn_slots > self.spawn_slots {
+            return Err(format!(
+                "preferredSpawnSlots ({}) cannot exceed spawnSlots ({})",
+                self.preferred_spawn_slots, self.spawn_slots
+            ));
+        }
+        let (low, high) = self.shop_price_range;
+        if !(low >= 0.0 && low <= high && high.is_finite()) {
+            return Err(format!(
+                "shopPriceRange has to be a finite, non-negative ascending range, got {low} to {high}"
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 /// Configuration parameter for a header
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -915,4 +1380,66 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn tuning_defaults() {
+        let config = "{\"seed\":\"3027801186584776\",\"worldSettings\":[{\"spawn\":\"MarshSpawn.Main\",\"difficulty\":\"Moki\",\"tricks\":[],\"hard\":false,\"goals\":[],\"headers\":[],\"headerConfig\":[],\"inlineHeaders\":[]}],\"disableLogicFilter\":false,\"online\":false,\"createGame\":\"None\"}";
+        let universe_settings = UniverseSettings::parse(config).unwrap();
+        assert_eq!(universe_settings.tuning, GenerationTuning::default());
+        assert!(universe_settings.tuning.validate().is_ok());
+
+        let tuning: GenerationTuning = serde_json::from_str("{\"retries\":0}").unwrap();
+        assert_eq!(tuning.placeholder_slots, PLACEHOLDER_SLOTS);
+        assert!(tuning.validate().is_err());
+    }
+
+    #[test]
+    fn contradicting_goals() {
+        let custom = |flag: &str, conditions: &[&str]| {
+            Goal::Custom(CustomGoal {
+                flag: flag.to_string(),
+                conditions: conditions
+                    .iter()
+                    .map(|condition| condition.parse().unwrap())
+                    .collect(),
+            })
+        };
+
+        let mut goals = GoalModes::default();
+        goals.add(Goal::Relics(3)).unwrap();
+        assert!(goals.add(Goal::RelicChance(0.5)).is_err());
+        goals.add(custom("Bosses", &["9|0>=2", "9|1"])).unwrap();
+        goals.add(custom("Bosses", &["9|0>=2", "9|1"])).unwrap();
+        assert!(goals.add(custom("Bosses", &["9|0>=3"])).is_err());
+        assert!(goals.add(custom("All Trees", &[])).is_err());
+        assert!(goals.add(custom("All Wisps", &["9|2"])).is_err());
+        assert!(goals.add(custom("Nothing", &[])).is_err());
+        assert!(goals.add(custom("", &["9|2"])).is_err());
+        assert!(goals.add(custom("Few", &["9|0<2"])).is_err());
+        assert!(goals.add(custom("Exact", &["9|3=1", "9|3>1"])).is_err());
+        goals.add(custom("More", &["9|0<=2", "9|3>1"])).unwrap();
+        assert_eq!(goals.len(), 3);
+
+        let json = serde_json::to_string(&goals).unwrap();
+        assert_eq!(serde_json::from_str::<GoalModes>(&json).unwrap(), goals);
+    }
+
+    #[test]
+    fn custom_goal_met() {
+        let goal = CustomGoal {
+            flag: "Bosses".to_string(),
+            conditions: vec!["9|0>=2".parse().unwrap(), "9|1".parse().unwrap()],
+        };
+        let met = |first: f32, second: f32| {
+            goal.is_met(|identifier| match identifier.uber_id {
+                0 => first,
+                _ => second,
+            })
+        };
+
+        assert!(!met(0., 0.));
+        assert!(!met(2., 0.));
+        assert!(!met(1., 1.));
+        assert!(met(2., 1.));
+    }
 }
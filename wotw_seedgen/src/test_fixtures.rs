@@ -0,0 +1,10 @@
+//! Minimal logic input files shared between test modules
+
+/// Two pickups in Inkwater Marsh, `MarshSpawn.RockHC` and `MarshSpawn.FirstPickupEX`
+pub(crate) const LOCATIONS: &str = "\
+NodeIdentifier, Zone, PickupType, PickupDetails, UberGroupName, UberGroup, UberIdName, UberId, UberStateValue, X, Y, MapX, MapY
+MarshSpawn.RockHC, Inkwater Marsh, Resource, Life, swampStateGroup, 21786, healthContainerA, 60210, , -958.6, -4313.2, -958.6, -4313.2
+MarshSpawn.FirstPickupEX, Inkwater Marsh, SpiritLight, 50, swampStateGroup, 21786, smallExpA, 49485, , -764.1, -4313.4, -764.1, -4313.4
+";
+/// No named states
+pub(crate) const STATES: &str = "NodeIdentifier, UberGroup, UberId, UberStateValue\n";
@@ -7,6 +7,7 @@ use std::{
 };
 
 use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
 use wotw_seedgen_derive::VVariant;
 
 use crate::header::{parser, vdisplay, CodeDisplay, VResolve};
@@ -67,6 +68,17 @@ impl UberIdentifier {
         CodeDisplay::new(self, |s, f| write!(f, "{}|{}", s.uber_group, s.uber_id))
     }
 
+    /// Returns the name of this uberState, if it is known
+    ///
+    /// Names used by the randomizer take precedence over the names from the game
+    pub fn name(&self) -> Option<&'static str> {
+        rando_data::NAMED_UBER_STATES
+            .iter()
+            .chain(game_data::UBER_STATES)
+            .find(|(_, identifier)| self == identifier)
+            .map(|(name, _)| *name)
+    }
+
     pub fn is_shop(&self) -> bool {
         matches!(self.uber_group, 1 | 2)
             || self.uber_group == 48248 && matches!(self.uber_id, 19396 | 57987 | 41666)
@@ -102,23 +114,15 @@ impl UberIdentifier {
 }
 impl Display for UberIdentifier {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        rando_data::NAMED_UBER_STATES
-            .iter()
-            .find(|(_, identifier)| self == identifier)
-            .map_or_else(
-                || {
-                    game_data::UBER_STATES
-                        .iter()
-                        .find(|(_, identifier)| self == identifier)
-                        .map_or_else(|| self.code().to_string(), |(name, _)| (*name).to_string())
-                },
-                |(name, _)| (*name).to_string(),
-            )
-            .fmt(f)
+        match self.name() {
+            Some(name) => name.fmt(f),
+            None => self.code().to_string().fmt(f),
+        }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, VVariant)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, VVariant, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "&str")]
 pub struct UberStateTrigger {
     pub identifier: UberIdentifier,
     #[VType]
@@ -176,6 +180,17 @@ impl FromStr for UberStateTrigger {
         Ok(item)
     }
 }
+impl From<UberStateTrigger> for String {
+    fn from(trigger: UberStateTrigger) -> String {
+        trigger.code().to_string()
+    }
+}
+impl TryFrom<&str> for UberStateTrigger {
+    type Error = String;
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        UberStateTrigger::from_str(code)
+    }
+}
 impl UberStateTrigger {
     pub fn code(&self) -> CodeDisplay<UberStateTrigger> {
         CodeDisplay::new(self, |s, f| {
@@ -6,6 +6,7 @@ use ansi_term::Colour;
 
 pub const DEFAULT_SPAWN: &str = "MarshSpawn.Main";
 pub const TP_ANCHOR: &str = "Teleporters";
+pub const FINISH_ANCHOR: &str = "WillowsEnd.Upper"; // The door to the final fight
 pub const SPAWN_GRANTS: &[(&str, Item)] = &[(
     "EastPools.Teleporter",
     Item::Teleporter(Teleporter::EastLuma),
@@ -45,6 +46,11 @@ pub const WISP_STATES: &[UberIdentifier] = &[
     UberIdentifier::new(18793, 63291),
     UberIdentifier::new(10289, 22102),
 ];
+pub const TREE_GROUP: u16 = 0; // The uberGroup of the Ancestral Trees
+pub const JUNK_SPIRIT_LIGHT: u32 = 100; // Average Spirit Light per location when filling the pool with junk items
+pub const MULTIWORLD_GROUP: u16 = 12; // The uberGroup used to send items between worlds
+pub const CUSTOM_GOAL_GROUP: u16 = 16; // The uberGroup set once the conditions of a custom goal are met
+pub const CUSTOM_GOAL_COMMENT: &str = "// Custom goal: "; // Precedes the lines tracking a custom goal in the seed file
 
 pub const SPAWN_SLOTS: usize = 7;
 pub const PREFERRED_SPAWN_SLOTS: usize = 3;
@@ -213,7 +213,7 @@ pub enum RefillValue {
     Health(f32),
     Energy(f32),
 }
-#[derive(Debug, Clone, PartialEq, Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, Serialize, Deserialize)]
 pub enum NodeKind {
     Anchor,
     Pickup,
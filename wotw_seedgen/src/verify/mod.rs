@@ -0,0 +1,606 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use itertools::Itertools;
+use rustc_hash::FxHashSet;
+
+use crate::{
+    generator::location_goal_met,
+    item::{Command, Item, Resource},
+    log,
+    reach_check::apply_sets,
+    settings::{CustomGoal, Goal, UniverseSettings, WorldSettings},
+    uber_state::{UberIdentifier, UberStateComparator, UberStateCondition, UberStateTrigger},
+    util::{
+        self,
+        constants::{CUSTOM_GOAL_COMMENT, CUSTOM_GOAL_GROUP, KEYSTONE_DOORS, MULTIWORLD_GROUP},
+    },
+    world::{graph::Graph, World},
+};
+
+/// Error returned by [`verify_seed`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationError {
+    /// The amount of seed files doesn't match the amount of worlds in the settings
+    WorldCount { seed_files: usize, worlds: usize },
+    /// A seed file references something the logic doesn't contain
+    InvalidSeedFile { world_index: usize, message: String },
+}
+impl Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerificationError::WorldCount { seed_files, worlds } => {
+                write!(f, "Received {seed_files} seed files for {worlds} worlds")
+            }
+            VerificationError::InvalidSeedFile {
+                world_index,
+                message,
+            } => write!(f, "(World {world_index}): {message}"),
+        }
+    }
+}
+impl Error for VerificationError {}
+
+/// Findings of [`verify_seed`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerificationReport {
+    /// Findings for each world, in the order of the seed files
+    pub worlds: Vec<WorldVerification>,
+}
+/// Findings of [`verify_seed`] for a single world
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WorldVerification {
+    /// Whether the final fight can be reached
+    pub finish_reachable: bool,
+    /// [`Goal`]s that cannot be achieved
+    pub unmet_goals: Vec<Goal>,
+    /// Identifiers of pickup locations that can never be reached
+    pub unreachable_locations: Vec<String>,
+    /// Points during the playthrough where spending Keystones on the wrong doors may softlock
+    pub keystone_risks: Vec<KeystoneRisk>,
+    /// Lines of the seed file that could not be read as placements
+    pub skipped_lines: Vec<String>,
+}
+/// More Keystone doors were available than the remaining Keystones could open
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeystoneRisk {
+    /// Identifiers of the available doors that haven't been opened yet
+    pub doors: Vec<String>,
+    /// Keystones needed to open all of the `doors`
+    pub required: u32,
+    /// Keystones left at this point after opening the doors that became available earlier
+    pub owned: u32,
+}
+
+impl VerificationReport {
+    /// Checks whether every world can reach the final fight with all of its [`Goal`]s achieved
+    pub fn is_beatable(&self) -> bool {
+        self.worlds.iter().all(WorldVerification::is_beatable)
+    }
+}
+impl WorldVerification {
+    /// Checks whether the final fight can be reached with all [`Goal`]s achieved
+    pub fn is_beatable(&self) -> bool {
+        self.finish_reachable && self.unmet_goals.is_empty()
+    }
+}
+
+impl Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let multiworld = self.worlds.len() > 1;
+
+        for (index, world) in self.worlds.iter().enumerate() {
+            if multiworld {
+                writeln!(f, "World [{index}]:")?;
+            }
+
+            if world.is_beatable() {
+                writeln!(f, "Beatable")?;
+            } else {
+                writeln!(f, "Not beatable")?;
+            }
+            if !world.finish_reachable {
+                writeln!(f, "  The final fight cannot be reached")?;
+            }
+            if !world.unmet_goals.is_empty() {
+                writeln!(
+                    f,
+                    "  Unmet goals: {}",
+                    world.unmet_goals.iter().format(", ")
+                )?;
+            }
+            if !world.unreachable_locations.is_empty() {
+                writeln!(
+                    f,
+                    "  {} unreachable locations: {}",
+                    world.unreachable_locations.len(),
+                    world.unreachable_locations.iter().format(", ")
+                )?;
+            }
+            for risk in &world.keystone_risks {
+                writeln!(
+                    f,
+                    "  Possible keylock: {} Keystones for {} ({} required)",
+                    risk.owned,
+                    risk.doors.iter().format(", "),
+                    risk.required
+                )?;
+            }
+            if !world.skipped_lines.is_empty() {
+                writeln!(
+                    f,
+                    "  Skipped {} unreadable lines",
+                    world.skipped_lines.len()
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Simulates collecting everything in a finished seed to check whether each world can be completed
+///
+/// `seed_files` should contain the seed file of every world, as generated by [`generate_seed`](crate::generator::generate_seed)
+///
+/// Items sent between worlds are followed through the multiworld uberStates
+pub fn verify_seed(
+    graph: &Graph,
+    seed_files: &[String],
+    settings: &UniverseSettings,
+) -> Result<VerificationReport, VerificationError> {
+    if seed_files.len() != settings.world_settings.len() {
+        return Err(VerificationError::WorldCount {
+            seed_files: seed_files.len(),
+            worlds: settings.world_settings.len(),
+        });
+    }
+
+    let mut reports = vec![WorldVerification::default(); seed_files.len()];
+    let mut worlds = Vec::with_capacity(seed_files.len());
+    let mut spawns = Vec::with_capacity(seed_files.len());
+
+    for (world_index, ((seed_file, world_settings), report)) in seed_files
+        .iter()
+        .zip(&settings.world_settings)
+        .zip(&mut reports)
+        .enumerate()
+    {
+        let invalid = |message| VerificationError::InvalidSeedFile {
+            world_index,
+            message,
+        };
+
+        let mut world = World::new_spawn(graph, world_settings);
+        world.goals = seed_goals(world_settings, seed_file);
+        report.skipped_lines = preplace_seed(&mut world, seed_file);
+        apply_sets(&mut world, seed_file).map_err(invalid)?;
+
+        let spawn_identifier = util::spawn_from_seed(seed_file).map_err(invalid)?;
+        spawns.push(graph.find_spawn(&spawn_identifier).map_err(invalid)?);
+
+        world.set_uber_state(UberIdentifier::spawn(), 1.);
+        world.set_uber_state(UberIdentifier::load(), 1.);
+        worlds.push(world);
+    }
+
+    let mut reached = vec![FxHashSet::default(); worlds.len()];
+    let mut opened_doors = vec![FxHashSet::default(); worlds.len()];
+
+    loop {
+        let mut progress = false;
+
+        for world_index in 0..worlds.len() {
+            let world = &worlds[world_index];
+            let mut new_reached = world
+                .graph
+                .reached_locations(
+                    &world.player,
+                    spawns[world_index],
+                    world.uber_states(),
+                    &world.sets,
+                )
+                .into_iter()
+                .filter(|node| reached[world_index].insert(node.index()))
+                .collect::<Vec<_>>();
+            if new_reached.is_empty() {
+                continue;
+            }
+            progress = true;
+
+            new_reached
+                .sort_unstable_by_key(|node| node.trigger().map_or(0, UberStateTrigger::set_value));
+            for node in new_reached {
+                if let Some(trigger) = node.trigger() {
+                    let value = trigger.set_value() as f32;
+                    worlds[world_index].set_incremental_uber_state(trigger.identifier, value);
+                }
+            }
+
+            if let Some(risk) = keystone_risk(
+                &worlds[world_index],
+                &reached[world_index],
+                &mut opened_doors[world_index],
+            ) {
+                let risks = &mut reports[world_index].keystone_risks;
+                if risks.last() != Some(&risk) {
+                    log::trace!(
+                        "(World {}): Possible keylock with {} Keystones",
+                        world_index,
+                        risk.owned
+                    );
+                    risks.push(risk);
+                }
+            }
+        }
+
+        progress |= sync_multiworld_states(&mut worlds);
+
+        if !progress {
+            break;
+        }
+    }
+
+    // Anchors aren't part of the reached locations, but the final fight is checked through its anchor
+    for ((world, reached), spawn) in worlds.iter().zip(&mut reached).zip(&spawns) {
+        let anchors =
+            world
+                .graph
+                .reached_anchors(&world.player, spawn, world.uber_states(), &world.sets);
+        reached.extend(anchors.into_iter().map(|node| node.index()));
+    }
+
+    for (world_index, (world, report)) in worlds.iter().zip(&mut reports).enumerate() {
+        let reached = &reached[world_index];
+
+        report.finish_reachable = location_goal_met(None, world, reached).unwrap_or_default();
+        report.unmet_goals = world
+            .goals
+            .iter()
+            .filter(|&goal| {
+                !location_goal_met(Some(goal), world, reached)
+                    .unwrap_or_else(|| relics_collected(world))
+            })
+            .cloned()
+            .collect();
+        report.unreachable_locations = graph
+            .nodes
+            .iter()
+            .filter(|node| node.can_place() && !reached.contains(&node.index()))
+            .map(|node| node.identifier().to_string())
+            .collect();
+    }
+
+    Ok(VerificationReport { worlds: reports })
+}
+
+/// Collects the [`Goal`]s of the settings and those the seed file declares, which includes [`Goal`]s added by headers
+///
+/// Built-in [`Goal`]s are read from the flags, [`CustomGoal`]s from the lines written for them during generation
+fn seed_goals(world_settings: &WorldSettings, seed_file: &str) -> Vec<Goal> {
+    let relic_goal = world_settings
+        .goals
+        .iter()
+        .any(|goal| matches!(goal, Goal::Relics(_) | Goal::RelicChance(_)));
+
+    let mut declared = vec![];
+    let mut lines = seed_file.lines();
+    while let Some(line) = lines.next() {
+        if let Some(flags) = line.strip_prefix("Flags:") {
+            for flag in flags.split(',').map(str::trim) {
+                match flag {
+                    "All Wisps" => declared.push(Goal::Wisps),
+                    "All Trees" => declared.push(Goal::Trees),
+                    "All Quests" => declared.push(Goal::Quests),
+                    // The amount doesn't matter for verification, every placed Relic has to be collected
+                    "Relics" if !relic_goal => declared.push(Goal::Relics(0)),
+                    _ => {}
+                }
+            }
+        } else if let Some(flag) = line.strip_prefix(CUSTOM_GOAL_COMMENT) {
+            let conditions = lines
+                .next()
+                .and_then(parse_placement)
+                .and_then(|(_, item)| custom_goal_conditions(&item));
+            if let Some(conditions) = conditions {
+                declared.push(Goal::Custom(CustomGoal {
+                    flag: flag.to_string(),
+                    conditions,
+                }));
+            } else {
+                log::warning!("Failed to read the conditions of the custom goal {}", flag);
+            }
+        }
+    }
+
+    let mut goals = world_settings.goals.clone();
+    for goal in declared {
+        // A contradiction means the seed was generated with different settings, which take precedence
+        goals.add(goal).ok();
+    }
+
+    goals.into_iter().collect()
+}
+
+/// Reads the conditions back from the nested commands written for a [`CustomGoal`]
+fn custom_goal_conditions(mut item: &Item) -> Option<Vec<UberStateTrigger>> {
+    let mut conditions = vec![];
+
+    loop {
+        let (identifier, comparator, value, next) = match item {
+            Item::UberState(setter) if setter.identifier.uber_group == CUSTOM_GOAL_GROUP => {
+                return Some(conditions)
+            }
+            Item::Command(Command::IfEqual {
+                uber_identifier,
+                value,
+                item,
+            }) => (uber_identifier, UberStateComparator::Equals, value, item),
+            Item::Command(Command::IfGreater {
+                uber_identifier,
+                value,
+                item,
+            }) => (uber_identifier, UberStateComparator::Greater, value, item),
+            Item::Command(Command::IfLess {
+                uber_identifier,
+                value,
+                item,
+            }) => (uber_identifier, UberStateComparator::Less, value, item),
+            _ => return None,
+        };
+
+        let value = value.into_inner();
+        let condition = if comparator == UberStateComparator::Greater && value < 0. {
+            UberStateCondition {
+                comparator: UberStateComparator::GreaterOrEquals,
+                value: 0,
+            }
+        } else {
+            UberStateCondition {
+                comparator,
+                value: util::float_to_int(value).ok()?,
+            }
+        };
+        conditions.push(UberStateTrigger {
+            identifier: *identifier,
+            condition: Some(condition),
+        });
+        item = next;
+    }
+}
+
+/// Reads the placements of a seed file into the [`World`] and returns the lines that could not be read
+fn preplace_seed(world: &mut World, seed_file: &str) -> Vec<String> {
+    let mut skipped_lines = Vec::new();
+
+    for line in seed_file.lines() {
+        let line = line.split_once("//").map_or(line, |(line, _)| line).trim();
+        if line.is_empty() || line.starts_with("Flags:") || line.starts_with("Spawn:") {
+            continue;
+        }
+
+        match parse_placement(line) {
+            Some((trigger, item)) => world.preplace(trigger, item),
+            None => skipped_lines.push(line.to_string()),
+        }
+    }
+
+    skipped_lines
+}
+
+fn parse_placement(line: &str) -> Option<(UberStateTrigger, Item)> {
+    let mut parts = line.splitn(3, '|');
+    let trigger = format!("{}|{}", parts.next()?, parts.next()?);
+    let trigger = trigger.parse().ok()?;
+    let item = parts.next()?;
+    // Messages in seed files may carry a mute flag the item parser doesn't know
+    let item = item
+        .parse::<Item>()
+        .ok()
+        .or_else(|| item.strip_suffix("|mute")?.parse().ok())?;
+
+    Some((trigger, item))
+}
+
+/// Copies multiworld uberStates to every world that didn't receive them yet
+///
+/// Returns `true` if any uberState was copied
+fn sync_multiworld_states(worlds: &mut [World]) -> bool {
+    let sent = worlds
+        .iter()
+        .flat_map(|world| world.uber_states())
+        .filter(|(identifier, _)| identifier.uber_group == MULTIWORLD_GROUP)
+        .map(|(&identifier, &value)| (identifier, value))
+        .collect::<Vec<_>>();
+
+    let mut synced = false;
+    for (identifier, value) in sent {
+        for world in worlds.iter_mut() {
+            if world.get_uber_state(identifier) < value {
+                world.set_uber_state(identifier, value);
+                synced = true;
+            }
+        }
+    }
+
+    synced
+}
+
+/// Checks whether the reached doors that haven't been opened yet cost more Keystones than are left
+///
+/// Afterwards all reached doors are considered opened, so later steps only account for the newly available doors
+fn keystone_risk(
+    world: &World,
+    reached: &FxHashSet<usize>,
+    opened_doors: &mut FxHashSet<usize>,
+) -> Option<KeystoneRisk> {
+    let reached_doors = world
+        .graph
+        .nodes
+        .iter()
+        .filter(|node| reached.contains(&node.index()))
+        .filter_map(|node| {
+            KEYSTONE_DOORS
+                .iter()
+                .find(|(identifier, _)| *identifier == node.identifier())
+                .map(|door| (node.index(), door))
+        })
+        .collect::<Vec<_>>();
+
+    let spent = reached_doors
+        .iter()
+        .filter(|(index, _)| opened_doors.contains(index))
+        .map(|(_, (_, keystones))| keystones)
+        .sum::<u32>();
+    let doors = reached_doors
+        .iter()
+        .filter(|(index, _)| opened_doors.insert(*index))
+        .map(|(_, door)| door)
+        .collect::<Vec<_>>();
+
+    let required = doors.iter().map(|(_, keystones)| keystones).sum();
+    let owned = world
+        .player
+        .inventory
+        .get(&Item::Resource(Resource::Keystone))
+        .saturating_sub(spent);
+
+    (required > owned).then(|| KeystoneRisk {
+        doors: doors
+            .into_iter()
+            .map(|(identifier, _)| identifier.to_string())
+            .collect(),
+        required,
+        owned,
+    })
+}
+
+/// Checks whether every Relic placed for this world has been collected
+fn relics_collected(world: &World) -> bool {
+    world
+        .preplacements
+        .iter()
+        .filter(|(_, items)| items.iter().any(|item| matches!(item, Item::Relic(_))))
+        .all(|(trigger, _)| {
+            world.uber_states().contains_key(&trigger.identifier)
+                && trigger.check_value(world.get_uber_state(trigger.identifier))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        files::{self, NO_FILE_ACCESS},
+        generate_seed, logic,
+        settings::{GoalModes, InlineHeader},
+    };
+
+    #[test]
+    fn verify_generated_seed() {
+        let mut universe_settings = UniverseSettings::default();
+        universe_settings.world_settings[0].goals = GoalModes::from_iter([Goal::Wisps]);
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+
+        let seed = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).unwrap();
+        let seed_files = seed.seed_files().unwrap();
+        let report = verify_seed(&graph, &seed_files, &universe_settings).unwrap();
+        assert!(report.is_beatable(), "{report}");
+
+        let err = verify_seed(&graph, &[], &universe_settings);
+        assert_eq!(
+            err,
+            Err(VerificationError::WorldCount {
+                seed_files: 0,
+                worlds: 1
+            })
+        );
+    }
+
+    #[test]
+    fn verify_header_goals() {
+        let mut universe_settings = UniverseSettings::default();
+        universe_settings.world_settings[0].inline_headers = vec![InlineHeader {
+            name: Some("goals".to_string()),
+            content: "!!__goalmode_hack trees\n!!goal 21786|60210, 21786|49485>=1 Marsh Pickups\n"
+                .to_string(),
+        }];
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+
+        let seed = generate_seed(&graph, &NO_FILE_ACCESS, &universe_settings).unwrap();
+        let mut seed_files = seed.seed_files().unwrap();
+        let goals = seed_goals(&universe_settings.world_settings[0], &seed_files[0]);
+        assert_eq!(
+            goals,
+            [
+                Goal::Trees,
+                Goal::Custom(CustomGoal {
+                    flag: "Marsh Pickups".to_string(),
+                    conditions: vec![
+                        "21786|60210>0".parse().unwrap(),
+                        "21786|49485>0".parse().unwrap()
+                    ],
+                })
+            ]
+        );
+
+        let report = verify_seed(&graph, &seed_files, &universe_settings).unwrap();
+        assert!(report.is_beatable(), "{report}");
+
+        seed_files[0].push_str("// Custom goal: Never\n3|1|4|18|9|5|0|8|16|1|bool|true\n");
+        let report = verify_seed(&graph, &seed_files, &universe_settings).unwrap();
+        assert_eq!(
+            report.worlds[0].unmet_goals,
+            [Goal::Custom(CustomGoal {
+                flag: "Never".to_string(),
+                conditions: vec!["9|5>0".parse().unwrap()],
+            })]
+        );
+    }
+
+    #[test]
+    fn keystone_risks() {
+        let universe_settings = UniverseSettings::default();
+        let areas = files::read_file("areas", "wotw", "logic").unwrap();
+        let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+        let states = files::read_file("state_data", "csv", "logic").unwrap();
+        let graph =
+            logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
+        let door = |identifier| {
+            graph
+                .nodes
+                .iter()
+                .find(|node| node.identifier() == identifier)
+                .unwrap()
+                .index()
+        };
+
+        let mut world = World::new_spawn(&graph, &universe_settings.world_settings[0]);
+        world.grant_player(Item::Resource(Resource::Keystone), 2);
+        let mut opened_doors = FxHashSet::default();
+        let mut reached = FxHashSet::from_iter([door("MarshSpawn.KeystoneDoor")]);
+        assert_eq!(keystone_risk(&world, &reached, &mut opened_doors), None);
+
+        // The Keystones were spent on the first door
+        reached.insert(door("HowlsDen.KeystoneDoor"));
+        assert_eq!(
+            keystone_risk(&world, &reached, &mut opened_doors),
+            Some(KeystoneRisk {
+                doors: vec!["HowlsDen.KeystoneDoor".to_string()],
+                required: 2,
+                owned: 0,
+            })
+        );
+        assert_eq!(keystone_risk(&world, &reached, &mut opened_doors), None);
+    }
+}
@@ -0,0 +1,254 @@
+use std::sync::Arc;
+
+use rand::{seq::SliceRandom, Rng};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::{
+    graph::{Graph, Node},
+    Player, Pool,
+};
+use crate::{
+    generator::DoorConnection,
+    item::Item,
+    log,
+    settings::{Spawn, UniverseSettings, WorldSettings},
+};
+
+/// How many door layouts to try before giving up
+const DOOR_RETRIES: usize = 100;
+
+/// One side of a door, as the index of the anchor and the index of the door connection within that anchor
+pub(crate) type DoorSide = (usize, usize);
+/// The index of the anchor each shuffled door leads to
+pub(crate) type DoorLayout = FxHashMap<DoorSide, usize>;
+
+/// Checks that every door of the [`Graph`] has exactly one matching door back
+pub(crate) fn validate_doors(graph: &Graph) -> Result<(), String> {
+    door_sides(graph).map(|_| ())
+}
+
+/// Shuffles the doors of the [`Graph`] for one seed
+///
+/// Doors are shuffled in pairs, so going through a door and back through the one you arrived at will always return you to where you started
+///
+/// Every layout is checked with a [`Player`] who owns the entire item pool, and only accepted if it can reach every location that was reachable before shuffling.
+/// This is checked from every spawn the [`Spawn`] setting of each world may choose, since the spawn is only picked later during generation
+///
+/// Doors may not be paired with another door of the same anchor
+pub(crate) fn randomize_doors(
+    graph: &Graph,
+    settings: &UniverseSettings,
+    rng: &mut impl Rng,
+) -> Result<(Arc<DoorLayout>, Vec<DoorConnection>), String> {
+    let sides = door_sides(graph)?;
+    if sides.is_empty() {
+        log::warning!("Door randomization is enabled, but the logic contains no doors");
+        return Ok((Arc::default(), Vec::new()));
+    }
+
+    let vanilla_reach = reachable_locations(graph, settings, None)?;
+
+    for attempt in 1..=DOOR_RETRIES {
+        let mut shuffled = sides.clone();
+        shuffled.shuffle(rng);
+        if shuffled.chunks_exact(2).any(|pair| pair[0].0 == pair[1].0) {
+            continue;
+        }
+        let layout = Arc::new(
+            shuffled
+                .chunks_exact(2)
+                .flat_map(|pair| [(pair[0], pair[1].0), (pair[1], pair[0].0)])
+                .collect::<DoorLayout>(),
+        );
+
+        let reach = reachable_locations(graph, settings, Some(&layout))?;
+        if reach
+            .iter()
+            .zip(&vanilla_reach)
+            .all(|(reach, vanilla_reach)| reach.is_superset(vanilla_reach))
+        {
+            log::trace!("Found a door layout after {} attempts", attempt);
+
+            let doors = sides
+                .iter()
+                .map(|side| DoorConnection {
+                    from: graph.nodes[side.0].identifier().to_string(),
+                    to: graph.nodes[layout[side]].identifier().to_string(),
+                })
+                .collect();
+            return Ok((layout, doors));
+        }
+    }
+
+    Err(format!(
+        "Failed to find a door layout that keeps every location reachable after {DOOR_RETRIES} attempts"
+    ))
+}
+
+/// Finds all doors and makes sure each has exactly one matching door back
+fn door_sides(graph: &Graph) -> Result<Vec<DoorSide>, String> {
+    let mut sides = FxHashMap::default();
+
+    for node in &graph.nodes {
+        if let Node::Anchor(anchor) = node {
+            for (connection_index, connection) in anchor.connections.iter().enumerate() {
+                if connection.door
+                    && sides
+                        .insert((anchor.index, connection.to), connection_index)
+                        .is_some()
+                {
+                    return Err(format!(
+                        "Door from {} to {} is defined more than once",
+                        anchor.identifier,
+                        graph.nodes[connection.to].identifier()
+                    ));
+                }
+            }
+        }
+    }
+
+    let mut sides = sides
+        .iter()
+        .map(|(&(from, to), &connection_index)| {
+            if sides.contains_key(&(to, from)) {
+                Ok((from, connection_index))
+            } else {
+                Err(format!(
+                    "Door from {} to {} has no matching door back",
+                    graph.nodes[from].identifier(),
+                    graph.nodes[to].identifier()
+                ))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // Iteration order of the map is arbitrary, sort to keep the shuffle reproducible
+    sides.sort_unstable();
+
+    Ok(sides)
+}
+
+/// Indices of the locations reachable with the entire item pool, per world and possible spawn of that world
+fn reachable_locations(
+    graph: &Graph,
+    settings: &UniverseSettings,
+    layout: Option<&Arc<DoorLayout>>,
+) -> Result<Vec<FxHashSet<usize>>, String> {
+    let mut reachable_locations = Vec::new();
+
+    for world_settings in &settings.world_settings {
+        let mut player = Player::spawn(world_settings);
+        player.inventory.merge(Pool::preset().inventory);
+        player.inventory.grant(Item::SpiritLight(1), 10000);
+        player.doors = layout.cloned();
+
+        for spawn in possible_spawns(graph, world_settings)? {
+            reachable_locations.push(
+                graph
+                    .reached_locations(&player, spawn, &FxHashMap::default(), &[])
+                    .into_iter()
+                    .filter(|node| node.can_place())
+                    .map(Node::index)
+                    .collect(),
+            );
+        }
+    }
+
+    Ok(reachable_locations)
+}
+
+/// Every spawn the [`Spawn`] setting may choose
+fn possible_spawns<'a>(
+    graph: &'a Graph,
+    world_settings: &WorldSettings,
+) -> Result<Vec<&'a Node>, String> {
+    match &world_settings.spawn {
+        Spawn::Set(spawn) => Ok(vec![graph.find_spawn(spawn)?]),
+        Spawn::OneOf(spawns) => spawns.iter().map(|spawn| graph.find_spawn(spawn)).collect(),
+        Spawn::Weighted(spawns) => spawns
+            .iter()
+            .filter(|(_, weight)| *weight > 0)
+            .map(|(spawn, _)| graph.find_spawn(spawn))
+            .collect(),
+        Spawn::Random => {
+            let spawns = world_settings.difficulty.spawn_locations();
+            Ok(graph
+                .nodes
+                .iter()
+                .filter(|node| spawns.contains(&node.identifier()))
+                .collect())
+        }
+        Spawn::FullyRandom => Ok(graph.nodes.iter().filter(|node| node.can_spawn()).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic;
+    use crate::test_fixtures::{LOCATIONS, STATES};
+    use rand::rngs::StdRng;
+    use rand_seeder::Seeder;
+
+    const AREAS: &str = "
+anchor MarshSpawn.Main at 0, 0:
+  door RoomA: free
+  door RoomB: free
+
+anchor RoomA at 10, 0:
+  door MarshSpawn.Main: free
+  pickup MarshSpawn.RockHC: free
+
+anchor RoomB at 20, 0:
+  door MarshSpawn.Main: free
+  pickup MarshSpawn.FirstPickupEX: free
+";
+
+    #[test]
+    fn door_randomization() {
+        let mut universe_settings = UniverseSettings::default();
+        let graph = logic::parse_logic(AREAS, LOCATIONS, STATES, &universe_settings, true).unwrap();
+
+        for seed in 0..10 {
+            let mut rng: StdRng = Seeder::from(seed).make_rng();
+            let (layout, doors) = randomize_doors(&graph, &universe_settings, &mut rng).unwrap();
+
+            assert_eq!(layout.len(), 4);
+            assert_eq!(doors.len(), 4);
+            for door in &doors {
+                assert_ne!(door.from, door.to);
+                assert!(doors
+                    .iter()
+                    .any(|other| other.from == door.to && other.to == door.from));
+            }
+            assert_eq!(
+                reachable_locations(&graph, &universe_settings, Some(&layout)).unwrap()[0].len(),
+                2
+            );
+        }
+
+        let one_way = AREAS.replace(
+            "  door MarshSpawn.Main: free\n  pickup MarshSpawn.RockHC",
+            "  pickup MarshSpawn.RockHC",
+        );
+        assert!(logic::parse_logic(&one_way, LOCATIONS, STATES, &universe_settings, true).is_err());
+
+        let duplicate = AREAS.replace(
+            "  door RoomB: free\n",
+            "  door RoomA: free\n  door RoomB: free\n",
+        );
+        let err = logic::parse_logic(&duplicate, LOCATIONS, STATES, &universe_settings, true)
+            .err()
+            .unwrap();
+        assert!(err.contains("more than once"), "{err}");
+
+        let mut rng: StdRng = Seeder::from(0).make_rng();
+        universe_settings.world_settings[0].spawn = Spawn::Set("RoomA".to_string());
+        let (layout, _) = randomize_doors(&graph, &universe_settings, &mut rng).unwrap();
+        assert_eq!(
+            reachable_locations(&graph, &universe_settings, Some(&layout)).unwrap()[0].len(),
+            2
+        );
+        universe_settings.world_settings[0].spawn = Spawn::Set("Nowhere".to_string());
+        assert!(randomize_doors(&graph, &universe_settings, &mut rng).is_err());
+    }
+}
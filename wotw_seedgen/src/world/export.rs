@@ -0,0 +1,318 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+use serde::Serialize;
+use wotw_seedgen_derive::FromStr;
+
+use super::{
+    graph::{Graph, Node},
+    requirement::Requirement,
+};
+use crate::util::{NodeKind, Position, Zone};
+
+/// Output formats supported by [`Graph::export`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromStr)]
+#[ParseFromIdentifier]
+pub enum ExportFormat {
+    /// Graphviz DOT, each node is labeled with its identifier and zone and each connection with its requirement
+    Dot,
+    /// A json object containing a list of nodes and a list of edges
+    Json,
+}
+
+#[derive(Serialize)]
+struct ExportGraph<'a> {
+    nodes: Vec<ExportNode<'a>>,
+    edges: Vec<ExportEdge<'a>>,
+}
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportNode<'a> {
+    identifier: &'a str,
+    kind: NodeKind,
+    zone: Option<Zone>,
+    position: Option<&'a Position>,
+    map_position: Option<&'a Position>,
+}
+#[derive(Serialize)]
+struct ExportEdge<'a> {
+    from: &'a str,
+    to: &'a str,
+    requirement: String,
+    door: bool,
+}
+
+impl Graph {
+    /// Exports the nodes and connections of the [`Graph`] for use in external tools
+    ///
+    /// Requirements are written in a simplified form similar to the syntax of areas.wotw
+    pub fn export(&self, format: ExportFormat) -> String {
+        let export = self.export_graph();
+        match format {
+            ExportFormat::Dot => export.dot(),
+            // This is safe because the export is known to serialize successfully
+            ExportFormat::Json => serde_json::to_string_pretty(&export).unwrap(),
+        }
+    }
+
+    fn export_graph(&self) -> ExportGraph<'_> {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| ExportNode {
+                identifier: node.identifier(),
+                kind: node.node_kind(),
+                zone: node.zone(),
+                position: node.position(),
+                map_position: node.map_position(),
+            })
+            .collect();
+        let edges = self
+            .nodes
+            .iter()
+            .filter_map(|node| match node {
+                Node::Anchor(anchor) => Some(anchor),
+                _ => None,
+            })
+            .flat_map(|anchor| {
+                anchor.connections.iter().map(|connection| ExportEdge {
+                    from: &anchor.identifier,
+                    to: self.nodes[connection.to].identifier(),
+                    requirement: self.requirement_text(&connection.requirement),
+                    door: connection.door,
+                })
+            })
+            .collect();
+
+        ExportGraph { nodes, edges }
+    }
+
+    /// Writes a [`Requirement`] in a simplified form similar to the syntax of areas.wotw
+    ///
+    /// Nested groups of the same kind are flattened, redundant parts are removed and mixed groups are wrapped in parentheses
+    pub fn requirement_text(&self, requirement: &Requirement) -> String {
+        match simplify(requirement) {
+            Simplified::Requirement(requirement) => self.single_requirement_text(requirement),
+            Simplified::And(ands) => self.group_text(&ands, ", "),
+            Simplified::Or(ors) => self.group_text(&ors, " OR "),
+        }
+    }
+
+    fn group_text(&self, requirements: &[&Requirement], separator: &str) -> String {
+        requirements
+            .iter()
+            .map(|requirement| match requirement {
+                Requirement::And(_) | Requirement::Or(_) => {
+                    format!("({})", self.requirement_text(requirement))
+                }
+                _ => self.single_requirement_text(requirement),
+            })
+            .unique()
+            .join(separator)
+    }
+
+    fn single_requirement_text(&self, requirement: &Requirement) -> String {
+        match requirement {
+            Requirement::Free => "free".to_string(),
+            Requirement::Impossible => "impossible".to_string(),
+            Requirement::Difficulty(difficulty) => format!("{difficulty:?}"),
+            Requirement::NormalGameDifficulty => "NormalGameDifficulty".to_string(),
+            Requirement::Trick(trick) => format!("{trick:?}"),
+            Requirement::Skill(skill) => format!("{skill:?}"),
+            Requirement::EnergySkill(skill, amount) => format!("{skill:?}={amount}"),
+            Requirement::NonConsumingEnergySkill(skill) => format!("{skill:?}=0"),
+            Requirement::SpiritLight(amount) => format!("SpiritLight={amount}"),
+            Requirement::Resource(resource, amount) => format!("{resource:?}={amount}"),
+            Requirement::Shard(shard) => format!("{shard:?}"),
+            Requirement::Teleporter(teleporter) => format!("{teleporter:?}TP"),
+            Requirement::Water => "Water".to_string(),
+            Requirement::State(index) => self.nodes[*index].identifier().to_string(),
+            Requirement::Damage(amount) => format!("Damage={amount}"),
+            Requirement::Danger(amount) => format!("Danger={amount}"),
+            Requirement::Combat(enemies) => format!(
+                "Combat={}",
+                enemies
+                    .iter()
+                    .map(|(enemy, amount)| if *amount == 1 {
+                        format!("{enemy:?}")
+                    } else {
+                        format!("{amount}x{enemy:?}")
+                    })
+                    .join("+")
+            ),
+            Requirement::Boss(health) => format!("Boss={health}"),
+            Requirement::BreakWall(health) => format!("BreakWall={health}"),
+            Requirement::ShurikenBreak(health) => format!("ShurikenBreak={health}"),
+            Requirement::SentryBreak(health) => format!("SentryBreak={health}"),
+            Requirement::And(_) | Requirement::Or(_) => self.requirement_text(requirement),
+        }
+    }
+}
+
+impl ExportGraph<'_> {
+    fn dot(&self) -> String {
+        let mut dot = String::from("digraph logic {\n");
+
+        for node in &self.nodes {
+            let shape = match node.kind {
+                NodeKind::Anchor => "box",
+                NodeKind::Pickup => "ellipse",
+                NodeKind::State => "diamond",
+                NodeKind::Quest => "hexagon",
+            };
+            let label = match node.zone {
+                Some(zone) => format!("{}\n{zone}", node.identifier),
+                None => node.identifier.to_string(),
+            };
+            write!(
+                dot,
+                "  {} [shape={shape}, label={}",
+                quote(node.identifier),
+                quote(&label)
+            )
+            .unwrap();
+            if let Some(position) = node.position {
+                write!(dot, ", pos=\"{},{}!\"", position.x, position.y).unwrap();
+            }
+            dot.push_str("];\n");
+        }
+
+        for edge in &self.edges {
+            write!(
+                dot,
+                "  {} -> {} [label={}",
+                quote(edge.from),
+                quote(edge.to),
+                quote(&edge.requirement)
+            )
+            .unwrap();
+            if edge.door {
+                dot.push_str(", style=dashed");
+            }
+            dot.push_str("];\n");
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn quote(string: &str) -> String {
+    format!(
+        "\"{}\"",
+        string
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+enum Simplified<'a> {
+    Requirement(&'a Requirement),
+    And(Vec<&'a Requirement>),
+    Or(Vec<&'a Requirement>),
+}
+
+/// Flattens nested groups of the same kind and removes parts that don't affect the result
+fn simplify(requirement: &Requirement) -> Simplified<'_> {
+    match requirement {
+        Requirement::And(nested) => {
+            let mut ands = Vec::with_capacity(nested.len());
+            for and in nested {
+                match simplify(and) {
+                    Simplified::Requirement(Requirement::Free) => {}
+                    Simplified::Requirement(Requirement::Impossible) => {
+                        return Simplified::Requirement(&Requirement::Impossible)
+                    }
+                    Simplified::Requirement(requirement) => ands.push(requirement),
+                    Simplified::And(nested) => ands.extend(nested),
+                    Simplified::Or(_) => ands.push(and),
+                }
+            }
+            match ands.len() {
+                0 => Simplified::Requirement(&Requirement::Free),
+                1 => Simplified::Requirement(ands[0]),
+                _ => Simplified::And(ands),
+            }
+        }
+        Requirement::Or(nested) => {
+            let mut ors = Vec::with_capacity(nested.len());
+            for or in nested {
+                match simplify(or) {
+                    Simplified::Requirement(Requirement::Impossible) => {}
+                    Simplified::Requirement(Requirement::Free) => {
+                        return Simplified::Requirement(&Requirement::Free)
+                    }
+                    Simplified::Requirement(requirement) => ors.push(requirement),
+                    Simplified::Or(nested) => ors.extend(nested),
+                    Simplified::And(_) => ors.push(or),
+                }
+            }
+            match ors.len() {
+                0 => Simplified::Requirement(&Requirement::Impossible),
+                1 => Simplified::Requirement(ors[0]),
+                _ => Simplified::Or(ors),
+            }
+        }
+        _ => Simplified::Requirement(requirement),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{LOCATIONS, STATES};
+    use crate::{logic, settings::UniverseSettings};
+
+    const AREAS: &str = "
+anchor MarshSpawn.Main at 0, 0:
+  conn RoomA:
+    moki: Bash, DoubleJump OR Launch
+  door RoomB: free
+
+anchor RoomA at 10, 0:
+  pickup MarshSpawn.RockHC: free
+
+anchor RoomB at 20, 0:
+  door MarshSpawn.Main: free
+";
+
+    #[test]
+    fn graph_export() {
+        let graph = logic::parse_logic(
+            AREAS,
+            LOCATIONS,
+            STATES,
+            &UniverseSettings::default(),
+            false,
+        )
+        .unwrap();
+
+        let json = graph.export(ExportFormat::Json);
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 5);
+        let edges = json["edges"].as_array().unwrap();
+        assert_eq!(edges.len(), 4);
+        assert!(edges.iter().any(|edge| edge["from"] == "MarshSpawn.Main"
+            && edge["to"] == "RoomA"
+            && edge["requirement"] == "Bash, (DoubleJump OR Launch)"));
+        assert!(edges.iter().any(|edge| edge["door"] == true));
+        let pickup = json["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|node| node["identifier"] == "MarshSpawn.RockHC")
+            .unwrap();
+        assert_eq!(pickup["kind"], "Pickup");
+        assert_eq!(pickup["zone"], "Marsh");
+
+        let dot = graph.export(ExportFormat::Dot);
+        assert!(dot.starts_with("digraph logic {"));
+        assert!(dot.contains(
+            "\"MarshSpawn.Main\" -> \"RoomA\" [label=\"Bash, (DoubleJump OR Launch)\"];"
+        ));
+        assert!(dot.contains("\"RoomB\" -> \"MarshSpawn.Main\" [label=\"free\", style=dashed];"));
+
+        assert_eq!("json".parse(), Ok(ExportFormat::Json));
+    }
+}
@@ -22,6 +22,8 @@ pub struct Refill {
 pub struct Connection {
     pub to: usize,
     pub requirement: Requirement,
+    /// Doors connect to another anchor that has a door back, door randomization may change where they lead
+    pub door: bool,
 }
 
 #[derive(Debug)]
@@ -154,7 +156,7 @@ struct ReachContext<'a, 'b, 'c> {
     player: &'b Player<'c>,
     progression_check: bool,
     states: FxHashSet<usize>,
-    state_progressions: FxHashMap<usize, Vec<(usize, &'a Connection)>>,
+    state_progressions: FxHashMap<usize, Vec<(usize, usize, &'a Connection)>>,
     world_state: FxHashMap<usize, OrbVariants>,
     reached: Vec<&'a Node>,
     progressions: Vec<(&'a Requirement, OrbVariants)>,
@@ -171,6 +173,22 @@ impl<'b, 'c> ReachContext<'_, 'b, 'c> {
             progressions: Default::default(),
         }
     }
+
+    /// Returns the index of the node a connection leads to, following shuffled doors
+    fn target(
+        &self,
+        anchor_index: usize,
+        connection_index: usize,
+        connection: &Connection,
+    ) -> usize {
+        match &self.player.doors {
+            Some(doors) if connection.door => doors
+                .get(&(anchor_index, connection_index))
+                .copied()
+                .unwrap_or(connection.to),
+            _ => connection.to,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -203,8 +221,8 @@ impl Graph {
         context: &mut ReachContext<'a, '_, '_>,
     ) {
         if let Some(connections) = context.state_progressions.get(&index) {
-            for (from, connection) in connections.clone() {
-                if context.world_state.contains_key(&connection.to) {
+            for (from, to, connection) in connections.clone() {
+                if context.world_state.contains_key(&to) {
                     // TODO loop with improved orbs?
                     continue;
                 }
@@ -214,7 +232,7 @@ impl Graph {
                     context.world_state[&from].clone(),
                 );
                 if !target_orbs.is_empty() {
-                    self.reach_recursion(&self.nodes[connection.to], target_orbs, context);
+                    self.reach_recursion(&self.nodes[to], target_orbs, context);
                 }
             }
         }
@@ -252,8 +270,9 @@ impl Graph {
                     }
                 }
 
-                for connection in &anchor.connections {
-                    if context.world_state.contains_key(&connection.to) {
+                for (connection_index, connection) in anchor.connections.iter().enumerate() {
+                    let to = context.target(anchor.index, connection_index, connection);
+                    if context.world_state.contains_key(&to) {
                         // TODO loop with improved orbs?
                         continue;
                     }
@@ -282,15 +301,15 @@ impl Graph {
                             }
                         } else {
                             for state in states {
-                                context
-                                    .state_progressions
-                                    .entry(state)
-                                    .or_default()
-                                    .push((anchor.index, connection));
+                                context.state_progressions.entry(state).or_default().push((
+                                    anchor.index,
+                                    to,
+                                    connection,
+                                ));
                             }
                         }
                     } else {
-                        self.reach_recursion(&self.nodes[connection.to], target_orbs, context);
+                        self.reach_recursion(&self.nodes[to], target_orbs, context);
                     }
                 }
             }
@@ -391,6 +410,27 @@ impl Graph {
 
         context.reached
     }
+    /// Returns the anchors that can be reached, which [`Graph::reached_locations`] doesn't include
+    pub fn reached_anchors<'a>(
+        &'a self,
+        player: &Player,
+        spawn: &'a Node,
+        extra_states: &FxHashMap<UberIdentifier, f32>,
+        sets: &[usize],
+    ) -> Reached<'a> {
+        let mut context =
+            ReachContext::new(player, false, self.collect_extra_states(extra_states, sets));
+
+        self.reach_recursion(spawn, smallvec![player.max_orbs()], &mut context);
+        self.reached_by_teleporter(&mut context);
+
+        context
+            .world_state
+            .keys()
+            .map(|&index| &self.nodes[index])
+            .filter(|node| matches!(node, Node::Anchor(_)))
+            .collect()
+    }
     pub fn reached_and_progressions<'a>(
         &'a self,
         player: &Player,
@@ -406,8 +446,8 @@ impl Graph {
 
         // add progressions containing states that were never met
         for (_, state_progressions) in context.state_progressions {
-            for (from, connection) in state_progressions {
-                if !context.world_state.contains_key(&connection.to) {
+            for (from, to, connection) in state_progressions {
+                if !context.world_state.contains_key(&to) {
                     context
                         .progressions
                         .push((&connection.requirement, context.world_state[&from].clone()));
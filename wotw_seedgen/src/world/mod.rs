@@ -1,8 +1,12 @@
+pub(crate) mod doors;
+pub mod export;
 pub mod graph;
 pub mod player;
 pub mod pool;
+mod reach_solutions;
 pub mod requirement;
 
+pub use export::ExportFormat;
 pub use graph::Graph;
 pub use player::Player;
 pub use pool::Pool;
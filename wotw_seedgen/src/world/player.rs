@@ -1,5 +1,8 @@
+use std::sync::Arc;
+
 use smallvec::{smallvec, SmallVec};
 
+use super::doors::DoorLayout;
 use crate::inventory::Inventory;
 use crate::item::{Item, Resource, Skill};
 use crate::settings::WorldSettings;
@@ -10,6 +13,8 @@ use crate::util::{orbs, OrbVariants, Orbs, RefillValue};
 pub struct Player<'a> {
     pub inventory: Inventory,
     pub settings: &'a WorldSettings,
+    /// Where the doors lead if they were shuffled by door randomization
+    pub(crate) doors: Option<Arc<DoorLayout>>,
 }
 impl Player<'_> {
     /// Returns an instance of [`Player`] with the given [`WorldSettings`]
@@ -20,6 +25,7 @@ impl Player<'_> {
         Player {
             inventory,
             settings,
+            doors: None,
         }
     }
     /// Returns an instance of [`Player`] with the given [`WorldSettings`]
@@ -33,6 +39,7 @@ impl Player<'_> {
         Player {
             inventory,
             settings,
+            doors: None,
         }
     }
 
@@ -4,6 +4,7 @@ use rustc_hash::FxHashMap;
 use crate::inventory::Inventory;
 use crate::item::{BonusUpgrade, Item, Resource, Shard, Skill};
 use crate::log;
+use crate::settings::{BasePool, ItemCount, ItemPoolSettings};
 
 // TODO the pool could be an inventory probably
 #[derive(Default, Debug, Clone)]
@@ -86,6 +87,26 @@ impl Pool {
         }
     }
 
+    /// Builds the [`Pool`] described by the [`ItemPoolSettings`]
+    ///
+    /// The [`JunkFill`](crate::settings::JunkFill) is not applied, since it depends on how many locations remain after headers
+    pub fn from_settings(settings: &ItemPoolSettings) -> Pool {
+        let mut pool = match settings.base {
+            BasePool::Standard => Pool::preset(),
+            BasePool::Empty => Pool::default(),
+        };
+
+        for ItemCount { item, count } in &settings.items {
+            pool.inventory.items.remove(item);
+            pool.grant(item.clone(), *count);
+        }
+        if let Some(spirit_light) = settings.spirit_light {
+            pool.spirit_light = spirit_light;
+        }
+
+        pool
+    }
+
     pub fn grant(&mut self, item: Item, amount: u32) {
         if let Item::SpiritLight(amount) = item {
             self.spirit_light += amount;
@@ -115,11 +136,17 @@ impl Pool {
         true
     }
 
-    pub fn choose_random(&self, multiworld_spread: bool, rng: &mut impl Rng) -> Option<&Item> {
+    pub fn choose_random(
+        &self,
+        multiworld_spread: bool,
+        allowed: impl Fn(&Item) -> bool,
+        rng: &mut impl Rng,
+    ) -> Option<&Item> {
         let mut items = self.inventory.items.iter().collect::<Vec<_>>();
         if multiworld_spread {
             items.retain(|&(item, _)| item.is_multiworld_spread());
         }
+        items.retain(|&(item, _)| allowed(item));
 
         loop {
             let (item, _) = *items.choose_weighted(rng, |&(_, amount)| amount).ok()?;
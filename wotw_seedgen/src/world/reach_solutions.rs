@@ -0,0 +1,154 @@
+use rustc_hash::FxHashMap;
+
+use super::{
+    graph::{Graph, Node},
+    requirement::filter_redundancies,
+    Player,
+};
+use crate::{inventory::Inventory, settings::WorldSettings};
+
+/// How many item sets [`Graph::solutions_to_reach`] may check before giving up on finding more results
+const SEARCH_LIMIT: usize = 1000;
+/// Item slots available to a single requirement, this is high enough to never limit the solutions
+const SLOTS: usize = 1000;
+
+impl Graph {
+    /// Returns minimal sets of items that let a fresh [`Player`] starting at `spawn` reach `node`
+    ///
+    /// `node` has to be the identifier of a pickup, state or quest, `spawn` the identifier of an anchor.
+    /// The items are in addition to the resources the player spawns with, see [`Player::spawn`].
+    ///
+    /// Item sets are explored in order of their size, so smaller solutions are found first.
+    /// At most `max_results` solutions are returned, and the search stops early on large logic gaps, so the result is not guaranteed to be exhaustive.
+    /// An empty result means no solution was found.
+    pub fn solutions_to_reach(
+        &self,
+        node: &str,
+        spawn: &str,
+        settings: &WorldSettings,
+        max_results: usize,
+    ) -> Result<Vec<Inventory>, String> {
+        let target = self
+            .nodes
+            .iter()
+            .find(|other| other.identifier() == node)
+            .ok_or_else(|| format!("Node {node} not found"))?;
+        if matches!(target, Node::Anchor(_)) {
+            return Err(format!(
+                "Can only find solutions to reach pickups, states and quests, {node} is an Anchor"
+            ));
+        }
+        let target = target.index();
+        let spawn = self.find_spawn(spawn)?;
+
+        let mut solutions = Vec::<Inventory>::new();
+        let mut pending = vec![Inventory::default()];
+        let mut checked = Vec::<Inventory>::new();
+
+        while solutions.len() < max_results && checked.len() < SEARCH_LIMIT {
+            let next = match pending
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, candidate)| candidate.item_count())
+            {
+                Some((index, _)) => pending.swap_remove(index),
+                None => break,
+            };
+            if checked.contains(&next) || solutions.iter().any(|solution| next.contains(solution)) {
+                continue;
+            }
+
+            let mut player = Player::spawn(settings);
+            player.inventory.merge(next.clone());
+            let (reached, progressions) =
+                self.reached_and_progressions(&player, spawn, &FxHashMap::default(), &[]);
+
+            if reached.iter().any(|node| node.index() == target) {
+                solutions.push(next.clone());
+            } else {
+                let states = reached
+                    .iter()
+                    .filter(|node| matches!(node, Node::State(_) | Node::Quest(_)))
+                    .map(|node| node.index())
+                    .collect();
+                for (requirement, orb_variants) in progressions {
+                    for solution in
+                        requirement.solutions(&player, &states, orb_variants, SLOTS, SLOTS)
+                    {
+                        let mut candidate = next.clone();
+                        candidate.merge(solution);
+                        pending.push(candidate);
+                    }
+                }
+            }
+
+            checked.push(next);
+        }
+
+        filter_redundancies(&mut solutions);
+        Ok(solutions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{LOCATIONS, STATES};
+    use crate::{
+        item::{Item, Skill},
+        logic,
+        settings::UniverseSettings,
+    };
+
+    const AREAS: &str = "
+anchor MarshSpawn.Main at 0, 0:
+  conn RoomA:
+    moki: Bash
+  conn RoomB:
+    moki: DoubleJump OR Launch
+
+anchor RoomA at 10, 0:
+  pickup MarshSpawn.RockHC:
+    moki: Glide
+
+anchor RoomB at 20, 0:
+  pickup MarshSpawn.RockHC: free
+";
+
+    #[test]
+    fn solutions_to_reach() {
+        let universe_settings = UniverseSettings::default();
+        let graph =
+            logic::parse_logic(AREAS, LOCATIONS, STATES, &universe_settings, false).unwrap();
+        let settings = &universe_settings.world_settings[0];
+
+        let skills = |skills: &[Skill]| {
+            let mut inventory = Inventory::default();
+            for skill in skills {
+                inventory.grant(Item::Skill(*skill), 1);
+            }
+            inventory
+        };
+        let solutions = graph
+            .solutions_to_reach("MarshSpawn.RockHC", "MarshSpawn.Main", settings, 10)
+            .unwrap();
+        assert_eq!(solutions.len(), 3);
+        for expected in [
+            skills(&[Skill::Bash, Skill::Glide]),
+            skills(&[Skill::DoubleJump]),
+            skills(&[Skill::Launch]),
+        ] {
+            assert!(solutions.contains(&expected), "{solutions:?}");
+        }
+
+        let solutions = graph
+            .solutions_to_reach("MarshSpawn.RockHC", "MarshSpawn.Main", settings, 1)
+            .unwrap();
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].item_count(), 1);
+
+        assert!(graph
+            .solutions_to_reach("RoomA", "MarshSpawn.Main", settings, 1)
+            .is_err());
+    }
+}
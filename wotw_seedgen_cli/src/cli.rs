@@ -8,10 +8,12 @@ use std::str::FromStr;
 use rustc_hash::FxHashSet;
 use structopt::StructOpt;
 
+use wotw_seedgen::generator::SpoilerFormat;
 use wotw_seedgen::item::{Shard, Skill, Teleporter};
 use wotw_seedgen::preset::{PresetGroup, PresetInfo, UniversePreset, WorldPreset};
 use wotw_seedgen::settings::{Difficulty, Goal, HeaderConfig, InlineHeader, Spawn, Trick};
 use wotw_seedgen::util::Zone;
+use wotw_seedgen::world::ExportFormat;
 
 #[derive(StructOpt)]
 /// Generate seeds for the Ori 2 randomizer.
@@ -58,12 +60,21 @@ pub enum SeedGenCommand {
         args: StatsArgs,
     },
     /// Deletes all cached seeds used to generate statistics
-    CleanStatsCache,
+    CleanStatsCache {
+        /// Delete this seed archive instead of the "seed_storage" folder
+        #[structopt(parse(from_os_str), long)]
+        seed_archive: Option<PathBuf>,
+    },
     /// Check which locations are in logic
     ReachCheck {
         #[structopt(flatten)]
         args: ReachCheckArgs,
     },
+    /// Inspect the logic
+    Logic {
+        #[structopt(subcommand)]
+        subcommand: LogicCommand,
+    },
     /// Inspect the available headers
     Headers {
         /// headers to look at in detail
@@ -121,6 +132,11 @@ pub struct SeedMetaArgs {
     /// If --tostdout is disabled, only spoilers will be written as json files.
     #[structopt(long)]
     pub json: bool,
+    /// additionally write the spoiler in these formats
+    ///
+    /// Available formats are text, json, csv, markdown and zones, which is a per-zone summary
+    #[structopt(long)]
+    pub spoiler_formats: Vec<SpoilerFormat>,
     /// launch the seed after generating
     #[structopt(short, long)]
     pub launch: bool,
@@ -261,8 +277,6 @@ fn resolve_flag_world_opts(
     }
 }
 
-pub type CannotError = String;
-
 /// Newtype to parse spawn flag
 #[derive(Clone)]
 pub struct SpawnOpt(Spawn);
@@ -272,12 +286,29 @@ impl SpawnOpt {
     }
 }
 impl FromStr for SpawnOpt {
-    type Err = CannotError;
+    type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let spawn = match &s.to_lowercase()[..] {
             "r" | "random" => Spawn::Random,
             "f" | "fullyrandom" => Spawn::FullyRandom,
+            _ if s.contains(':') => Spawn::Weighted(
+                s.split(',')
+                    .map(|part| {
+                        let (spawn, weight) = part
+                            .split_once(':')
+                            .ok_or_else(|| format!("Missing weight for spawn {part}"))?;
+                        let weight = weight
+                            .trim()
+                            .parse()
+                            .map_err(|_| format!("Invalid weight {weight} for spawn {spawn}"))?;
+                        Ok((spawn.trim().to_string(), weight))
+                    })
+                    .collect::<Result<_, String>>()?,
+            ),
+            _ if s.contains(',') => {
+                Spawn::OneOf(s.split(',').map(|part| part.trim().to_string()).collect())
+            }
             _ => Spawn::Set(s.to_string()),
         };
         Ok(SpawnOpt(spawn))
@@ -398,6 +429,8 @@ pub struct SeedSettings {
     /// Spawn destination
     ///
     /// Use an anchor name from the areas file, "r" / "random" for a random teleporter or "f" / "fullyrandom" for any location
+    ///
+    /// Separate multiple anchor names with commas to pick one of them, optionally adding weights like "MarshSpawn.Main:3,HowlsDen.Teleporter:1"
     #[structopt(short, long)]
     pub spawn: Vec<WorldOpt<SpawnOpt>>,
     /// Logically expected difficulty of execution you may be required to perform
@@ -434,6 +467,16 @@ pub struct SeedSettings {
     /// Disallow the use of the In-Logic filter while playing the seed
     #[structopt(short = "L", long)]
     pub disable_logic_filter: bool,
+    /// Determine which placements are required to finish each world and add them to the spoiler
+    ///
+    /// This makes generation noticeably slower
+    #[structopt(long)]
+    pub required_items: bool,
+    /// Shuffle the connections marked as doors in the logic
+    ///
+    /// This is experimental and needs a preset that enables experimentalDoors in the tuning, since the randomizer client can't apply door layouts yet
+    #[structopt(long)]
+    pub door_randomization: bool,
     /// Require an online connection to play the seed
     ///
     /// This is needed for Co-op, Multiworld and Bingo
@@ -469,6 +512,8 @@ impl SeedSettings {
             header_config,
             inline_headers,
             disable_logic_filter,
+            required_items,
+            door_randomization,
             online,
             seed,
         } = self;
@@ -488,6 +533,8 @@ impl SeedSettings {
         } else {
             None
         };
+        let required_items = if required_items { Some(true) } else { None };
+        let door_randomization = if door_randomization { Some(true) } else { None };
         let online = if online { Some(true) } else { None };
 
         let yes_fun = world_presets
@@ -531,6 +578,9 @@ impl SeedSettings {
                                 .map(InlineHeaderOpt::into_inner)
                                 .collect(),
                         ),
+                        placement_constraints: None,
+                        hints: None,
+                        item_pool: None,
                     }
                 },
             )
@@ -544,6 +594,9 @@ impl SeedSettings {
             seed,
             online,
             create_game: None,
+            tuning: None,
+            required_items,
+            door_randomization,
         })
     }
 }
@@ -570,6 +623,8 @@ pub struct WorldPresetSettings {
     /// Spawn destination
     ///
     /// Use an anchor name from the areas file, "r" / "random" for a random teleporter or "f" / "fullyrandom" for any location
+    ///
+    /// Separate multiple anchor names with commas to pick one of them, optionally adding weights like "MarshSpawn.Main:3,HowlsDen.Teleporter:1"
     #[structopt(short, long)]
     pub spawn: Option<SpawnOpt>,
     /// Logically expected difficulty of execution you may be required to perform
@@ -641,6 +696,9 @@ impl WorldPresetSettings {
                     .map(InlineHeaderOpt::into_inner)
                     .collect()
             }),
+            placement_constraints: None,
+            hints: None,
+            item_pool: None,
         }
     }
 }
@@ -692,6 +750,21 @@ pub struct StatsArgs {
     /// cleans the cache for the provided settings and generates new seeds from scratch
     #[structopt(short = "O", long)]
     pub overwrite_cache: bool,
+    /// Cache seeds in this single archive file instead of the "seed_storage" folder
+    ///
+    /// An archive can hold any amount of seeds for any settings and may be copied to share the cached seeds
+    #[structopt(parse(from_os_str), long)]
+    pub seed_archive: Option<PathBuf>,
+    /// Compare the statistics against the same settings with these universe presets applied on top
+    ///
+    /// Instead of the usual statistics, this will create "<title> comparison.csv" files with the difference for each result and how significant it is
+    #[structopt(long)]
+    pub compare_presets: Vec<String>,
+    /// Compare the statistics against the same settings using this logic file instead
+    ///
+    /// Can be combined with --compare-presets. Seeds generated with this logic file won't be cached
+    #[structopt(parse(from_os_str), long)]
+    pub compare_areas: Option<PathBuf>,
     #[structopt(flatten)]
     pub settings: SeedSettings,
 }
@@ -726,6 +799,12 @@ pub enum Analyzer {
     LocationItem { location: String },
     /// Analyzes which items get placed as forced progression
     Progression,
+    /// Analyzes how many spheres of progression a seed takes
+    /// You can optionally pass seed-length:<result-bucket-size> to group results together in buckets
+    SeedLength {
+        #[structopt(default_value = "1")]
+        result_bucket_size: NonZeroUsize,
+    },
     /// Analyzes the amount of spawn items. Mostly makes sense with random spawn, since with the default spawn usually no spawn items are given
     SpawnItemCount,
     /// Analyzes the spawn items. Mostly makes sense with random spawn, since with the default spawn usually no spawn items are given
@@ -734,6 +813,14 @@ pub enum Analyzer {
     SpawnLocation,
     /// Analyzes the spawn region. Useful for fully random spawn where there are a lot of spawn locations
     SpawnRegion,
+    /// Analyzes in which sphere of progression an item is placed.
+    /// Pass sphere-depth:<item-name> to specify which item to analyze (Example: "sphere-depth:Launch")
+    /// You can optionally pass sphere-depth:<item-name>,<result-bucket-size> to group results together in buckets
+    SphereDepth {
+        item: String,
+        #[structopt(default_value = "1")]
+        result_bucket_size: NonZeroUsize,
+    },
     /// Analyzes how big the steps of progression are
     /// You can optionally pass step-size:<result-bucket-size> to group results together in buckets
     StepSize {
@@ -856,6 +943,136 @@ pub enum HeaderCommand {
         #[structopt(parse(from_os_str))]
         path: PathBuf,
     },
+    /// Format headers into the canonical style
+    Format {
+        /// A file to format, or leave empty to format all headers in the directory
+        #[structopt(parse(from_os_str))]
+        path: Option<PathBuf>,
+        /// Only report headers that aren't formatted instead of changing them
+        #[structopt(long)]
+        check: bool,
+    },
+    /// Run header tests
+    ///
+    /// Tests are read from .wotwrht files and run against the header with the same name
+    Test {
+        /// A test file to run, or leave empty to run all header tests in the directory
+        #[structopt(parse(from_os_str))]
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(StructOpt)]
+pub enum LogicCommand {
+    /// Export the logic graph for visualization
+    ///
+    /// The graph is built for the provided settings, so requirements that the settings don't allow will show up as impossible
+    Export {
+        #[structopt(flatten)]
+        args: Box<LogicExportArgs>,
+    },
+    /// Check the logic for likely mistakes
+    ///
+    /// This reports anchors no connection leads to, pickup locations no anchor connects to, named requirements that are never used and requirements that can't be met on any difficulty
+    Lint {
+        /// the input file representing the logic
+        #[structopt(parse(from_os_str), default_value = "areas.wotw", long)]
+        areas: PathBuf,
+        /// the input file representing pickup locations
+        #[structopt(parse(from_os_str), default_value = "loc_data.csv", long)]
+        locations: PathBuf,
+    },
+    /// Compare two versions of the logic
+    ///
+    /// For each difficulty, this lists the locations whose minimal requirements from their directly connected anchors changed
+    Diff {
+        #[structopt(flatten)]
+        args: LogicDiffArgs,
+    },
+    /// Find the smallest sets of items needed to reach a location
+    Solutions {
+        #[structopt(flatten)]
+        args: LogicSolutionsArgs,
+    },
+}
+
+#[derive(StructOpt)]
+pub struct LogicDiffArgs {
+    /// the old version of the file representing the logic
+    #[structopt(parse(from_os_str))]
+    pub old_areas: PathBuf,
+    /// the new version of the file representing the logic
+    #[structopt(parse(from_os_str))]
+    pub new_areas: PathBuf,
+    /// the input file representing pickup locations
+    #[structopt(parse(from_os_str), default_value = "loc_data.csv", long)]
+    pub locations: PathBuf,
+    /// the input file representing state namings
+    #[structopt(parse(from_os_str), default_value = "state_data.csv", long)]
+    pub uber_states: PathBuf,
+    /// Logically expected tricks you may have to use
+    ///
+    /// Available tricks are "swordsentryjump", "hammersentryjump", "shurikenbreak", "sentrybreak", "hammerbreak", "spearbreak", "sentryburn", "removekillplane", "launchswap", "sentryswap", "flashswap", "blazeswap", "wavedash", "grenadejump", "hammerjump", "swordjump", "grenaderedirect", "sentryredirect", "pausehover", "glidejump", "glidehammerjump", "spearjump"
+    #[structopt(short, long)]
+    pub tricks: Vec<Trick>,
+    /// Logically assume hard in-game difficulty
+    #[structopt(long)]
+    pub hard: bool,
+}
+
+#[derive(StructOpt)]
+pub struct LogicExportArgs {
+    /// the input file representing the logic
+    #[structopt(parse(from_os_str), default_value = "areas.wotw", long)]
+    pub areas: PathBuf,
+    /// the input file representing pickup locations
+    #[structopt(parse(from_os_str), default_value = "loc_data.csv", long)]
+    pub locations: PathBuf,
+    /// the input file representing state namings
+    #[structopt(parse(from_os_str), default_value = "state_data.csv", long)]
+    pub uber_states: PathBuf,
+    /// the output format, either dot (for Graphviz) or json
+    #[structopt(long, default_value = "dot")]
+    pub format: ExportFormat,
+    /// the file to write the graph into, prints to the console if omitted
+    #[structopt(parse(from_os_str), long)]
+    pub output: Option<PathBuf>,
+    #[structopt(flatten)]
+    pub settings: SeedSettings,
+}
+
+#[derive(StructOpt)]
+pub struct LogicSolutionsArgs {
+    /// the identifier of the pickup, state or quest to reach
+    pub node: String,
+    /// the identifier of the anchor to start from
+    #[structopt(long, default_value = "MarshSpawn.Main")]
+    pub spawn: String,
+    /// how many item sets to list at most
+    #[structopt(short = "n", long, default_value = "10")]
+    pub max_results: usize,
+    /// the input file representing the logic
+    #[structopt(parse(from_os_str), default_value = "areas.wotw", long)]
+    pub areas: PathBuf,
+    /// the input file representing pickup locations
+    #[structopt(parse(from_os_str), default_value = "loc_data.csv", long)]
+    pub locations: PathBuf,
+    /// the input file representing state namings
+    #[structopt(parse(from_os_str), default_value = "state_data.csv", long)]
+    pub uber_states: PathBuf,
+    /// Logically expected difficulty of execution you may be required to perform
+    ///
+    /// Available difficulties are "moki", "gorlek", "kii", "unsafe"
+    #[structopt(short, long, default_value = "moki")]
+    pub difficulty: Difficulty,
+    /// Logically expected tricks you may have to use
+    ///
+    /// Available tricks are "swordsentryjump", "hammersentryjump", "shurikenbreak", "sentrybreak", "hammerbreak", "spearbreak", "sentryburn", "removekillplane", "launchswap", "sentryswap", "flashswap", "blazeswap", "wavedash", "grenadejump", "hammerjump", "swordjump", "grenaderedirect", "sentryredirect", "pausehover", "glidejump", "glidehammerjump", "spearjump"
+    #[structopt(short, long)]
+    pub tricks: Vec<Trick>,
+    /// Logically assume hard in-game difficulty
+    #[structopt(long)]
+    pub hard: bool,
 }
 
 #[derive(StructOpt)]
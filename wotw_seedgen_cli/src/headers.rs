@@ -4,13 +4,14 @@ use super::log_init;
 use std::fmt::Write;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use ansi_term::{Colour, Style};
 use log::LevelFilter;
 use rustc_hash::FxHashMap;
 
 use wotw_seedgen::files::{self, FileAccess, FILE_SYSTEM_ACCESS};
-use wotw_seedgen::header::{self, Header};
+use wotw_seedgen::header::{self, Header, HeaderCommand, HeaderTest};
 use wotw_seedgen::util::constants::NAME_COLOUR;
 
 pub fn headers(headers: Vec<String>, subcommand: Option<cli::HeaderCommand>) -> Result<(), String> {
@@ -20,6 +21,8 @@ pub fn headers(headers: Vec<String>, subcommand: Option<cli::HeaderCommand>) ->
     match subcommand {
         Some(cli::HeaderCommand::Validate { path }) => validate(path).map(|_| ()),
         Some(cli::HeaderCommand::Parse { path }) => compile_seed(path),
+        Some(cli::HeaderCommand::Format { path, check }) => format(path, check),
+        Some(cli::HeaderCommand::Test { path }) => test(path),
         None => {
             if headers.is_empty() {
                 list()
@@ -138,6 +141,35 @@ pub fn inspect(headers: Vec<String>) -> Result<(), String> {
             Some(description) => write!(output, "{description}\n\n").unwrap(),
             None => output.push_str("no description provided\n\n"),
         }
+
+        let parameters = Header::parse_parameters(&contents);
+        if !parameters.is_empty() {
+            output.push_str("Parameters:\n");
+            for parameter in parameters {
+                writeln!(
+                    output,
+                    "  {}: {}, default {}",
+                    NAME_COLOUR.paint(parameter.identifier),
+                    parameter.default.type_description(),
+                    parameter.default
+                )
+                .unwrap();
+                if let Some(documentation) = parameter.documentation {
+                    writeln!(output, "      {documentation}").unwrap();
+                }
+            }
+            output.push('\n');
+        }
+        // parse_parameters skips invalid declarations, report them instead
+        for command in contents
+            .lines()
+            .filter_map(|line| line.strip_prefix("!!"))
+            .filter(|command| command.starts_with("parameter "))
+        {
+            if let Err(err) = HeaderCommand::from_str(command) {
+                writeln!(output, "{} {err}", Colour::Red.paint("Invalid parameter:")).unwrap();
+            }
+        }
     }
 
     output.push_str(&hint);
@@ -155,6 +187,104 @@ pub fn validate(path: Option<PathBuf>) -> Result<(), String> {
     Ok(())
 }
 
+pub fn format(path: Option<PathBuf>, check: bool) -> Result<(), String> {
+    let paths = match path {
+        Some(mut path) => {
+            if path.extension().is_none() {
+                path.set_extension("wotwrh");
+            }
+            vec![path]
+        }
+        None => files::find_headers()?,
+    };
+
+    let mut unformatted = vec![];
+    for path in paths {
+        let content = read(&path)?;
+        let formatted = header::format_header(&content).map_err(|errors| {
+            format!(
+                "Failed to format {}:\n{}",
+                path.display(),
+                errors.verbose_display()
+            )
+        })?;
+
+        if formatted != content {
+            if check {
+                unformatted.push(path.display().to_string());
+            } else {
+                fs::write(&path, formatted)
+                    .map_err(|err| format!("Failed to write {}: {}", path.display(), err))?;
+                log::info!("Formatted {}", path.display());
+            }
+        }
+    }
+
+    if unformatted.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} header{} not formatted:\n{}",
+            unformatted.len(),
+            if unformatted.len() == 1 {
+                " is"
+            } else {
+                "s are"
+            },
+            unformatted.join("\n")
+        ))
+    }
+}
+
+pub fn test(path: Option<PathBuf>) -> Result<(), String> {
+    let paths = match path {
+        Some(mut path) => {
+            if path.extension().is_none() {
+                path.set_extension("wotwrht");
+            }
+            vec![path]
+        }
+        None => files::find_header_tests()?,
+    };
+
+    let mut output = String::new();
+    let (mut passed, mut failed) = (0, 0);
+    for path in paths {
+        let identifier = identifier(&path);
+        let test = HeaderTest::parse(&read(&path)?)
+            .map_err(|err| format!("Failed to parse {}: {}", path.display(), err))?;
+        // Prefer the header next to the test file
+        let header = read(path.with_extension("wotwrh"))
+            .or_else(|_| FILE_SYSTEM_ACCESS.read_header(&identifier))?;
+
+        for result in test.run(&header) {
+            let name = format!("{} {}", NAME_COLOUR.paint(&identifier), result.name);
+            if result.passed() {
+                passed += 1;
+                writeln!(output, "{name} ... {}", Colour::Green.paint("ok")).unwrap();
+            } else {
+                failed += 1;
+                writeln!(output, "{name} ... {}", Colour::Red.paint("FAILED")).unwrap();
+                for failure in result.failures {
+                    writeln!(output, "    {failure}").unwrap();
+                }
+            }
+        }
+    }
+
+    write!(output, "\n{passed} passed, {failed} failed").unwrap();
+    println!("{}", output);
+
+    if failed == 0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "{failed} header test{} failed",
+            if failed == 1 { "" } else { "s" }
+        ))
+    }
+}
+
 fn read(path: impl AsRef<Path>) -> Result<String, String> {
     fs::read_to_string(path.as_ref()).map_err(|err| err.to_string())
 }
@@ -0,0 +1,164 @@
+use super::cli;
+use super::log_init;
+
+use std::fs;
+use std::path::PathBuf;
+
+use log::LevelFilter;
+use rustc_hash::FxHashSet;
+
+use wotw_seedgen::files::FILE_SYSTEM_ACCESS;
+use wotw_seedgen::logic;
+use wotw_seedgen::settings::{UniverseSettings, WorldSettings};
+
+pub fn logic(subcommand: cli::LogicCommand) -> Result<(), String> {
+    log_init::initialize_log(None, LevelFilter::Info, false)
+        .unwrap_or_else(|err| eprintln!("Failed to initialize log: {err}"));
+
+    match subcommand {
+        cli::LogicCommand::Export { args } => export(*args),
+        cli::LogicCommand::Lint { areas, locations } => lint(areas, locations),
+        cli::LogicCommand::Diff { args } => diff(args),
+        cli::LogicCommand::Solutions { args } => solutions(args),
+    }
+}
+
+fn export(args: cli::LogicExportArgs) -> Result<(), String> {
+    let cli::LogicExportArgs {
+        areas,
+        locations,
+        uber_states,
+        format,
+        output,
+        settings: settings_args,
+    } = args;
+
+    let mut settings = UniverseSettings::default();
+    settings
+        .apply_preset(settings_args.into_universe_preset()?, &FILE_SYSTEM_ACCESS)
+        .map_err(|err| err.to_string())?;
+
+    let areas = fs::read_to_string(&areas)
+        .map_err(|err| format!("Failed to read {}: {}", areas.display(), err))?;
+    let locations = fs::read_to_string(&locations)
+        .map_err(|err| format!("Failed to read {}: {}", locations.display(), err))?;
+    let states = fs::read_to_string(&uber_states)
+        .map_err(|err| format!("Failed to read {}: {}", uber_states.display(), err))?;
+    let graph = logic::parse_logic(&areas, &locations, &states, &settings, false)?;
+
+    let export = graph.export(format);
+    match output {
+        Some(path) => {
+            fs::write(&path, export)
+                .map_err(|err| format!("Failed to write {}: {}", path.display(), err))?;
+            log::info!("Exported the logic to {}", path.display());
+        }
+        None => println!("{export}"),
+    }
+
+    Ok(())
+}
+
+fn lint(areas: PathBuf, locations: PathBuf) -> Result<(), String> {
+    let areas_source = fs::read_to_string(&areas)
+        .map_err(|err| format!("Failed to read {}: {}", areas.display(), err))?;
+    let locations = fs::read_to_string(&locations)
+        .map_err(|err| format!("Failed to read {}: {}", locations.display(), err))?;
+
+    let areas = logic::Areas::parse(&areas_source).map_err(|errors| errors.verbose_display())?;
+    let locations = logic::parse_locations(&locations)?;
+
+    let diagnostics = logic::lint(&areas, &locations);
+    for diagnostic in &diagnostics {
+        log::warn!(
+            "{}: {}",
+            diagnostic.kind,
+            diagnostic.verbose_display(&areas_source)
+        );
+    }
+    log::info!("Found {} potential problems", diagnostics.len());
+
+    Ok(())
+}
+
+fn diff(args: cli::LogicDiffArgs) -> Result<(), String> {
+    let cli::LogicDiffArgs {
+        old_areas,
+        new_areas,
+        locations,
+        uber_states,
+        tricks,
+        hard,
+    } = args;
+
+    let old_areas = fs::read_to_string(&old_areas)
+        .map_err(|err| format!("Failed to read {}: {}", old_areas.display(), err))?;
+    let new_areas = fs::read_to_string(&new_areas)
+        .map_err(|err| format!("Failed to read {}: {}", new_areas.display(), err))?;
+    let locations = fs::read_to_string(&locations)
+        .map_err(|err| format!("Failed to read {}: {}", locations.display(), err))?;
+    let states = fs::read_to_string(&uber_states)
+        .map_err(|err| format!("Failed to read {}: {}", uber_states.display(), err))?;
+
+    let world_settings = WorldSettings {
+        tricks: FxHashSet::from_iter(tricks),
+        hard,
+        ..WorldSettings::default()
+    };
+    let diff = logic::diff_logic(&old_areas, &new_areas, &locations, &states, &world_settings)?;
+    print!("{diff}");
+
+    Ok(())
+}
+
+fn solutions(args: cli::LogicSolutionsArgs) -> Result<(), String> {
+    let cli::LogicSolutionsArgs {
+        node,
+        spawn,
+        max_results,
+        areas,
+        locations,
+        uber_states,
+        difficulty,
+        tricks,
+        hard,
+    } = args;
+
+    let world_settings = WorldSettings {
+        difficulty,
+        tricks: FxHashSet::from_iter(tricks),
+        hard,
+        ..WorldSettings::default()
+    };
+    let universe_settings = UniverseSettings {
+        world_settings: vec![world_settings],
+        ..UniverseSettings::default()
+    };
+
+    let areas = fs::read_to_string(&areas)
+        .map_err(|err| format!("Failed to read {}: {}", areas.display(), err))?;
+    let locations = fs::read_to_string(&locations)
+        .map_err(|err| format!("Failed to read {}: {}", locations.display(), err))?;
+    let states = fs::read_to_string(&uber_states)
+        .map_err(|err| format!("Failed to read {}: {}", uber_states.display(), err))?;
+    let graph = logic::parse_logic(&areas, &locations, &states, &universe_settings, false)?;
+
+    let solutions = graph.solutions_to_reach(
+        &node,
+        &spawn,
+        &universe_settings.world_settings[0],
+        max_results,
+    )?;
+    if solutions.is_empty() {
+        println!("Found no way to reach {node} from {spawn}");
+    }
+    for solution in solutions {
+        if solution.items.is_empty() {
+            println!("free");
+        } else {
+            println!("{solution}");
+        }
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 mod cli;
 mod headers;
 mod log_init;
+mod logic;
 mod play;
 mod reach_check;
 mod seed;
@@ -29,12 +30,15 @@ fn main() -> ExitCode {
         }
         cli::SeedGenCommand::WorldPreset { args } => world_preset::create_world_preset(args),
         cli::SeedGenCommand::Stats { args } => stats::generate_stats(args),
-        cli::SeedGenCommand::CleanStatsCache => stats::clean_stats_cache(),
+        cli::SeedGenCommand::CleanStatsCache { seed_archive } => {
+            stats::clean_stats_cache(seed_archive)
+        }
         cli::SeedGenCommand::Headers {
             headers,
             subcommand,
         } => headers::headers(headers, subcommand),
         cli::SeedGenCommand::ReachCheck { args } => reach_check::reach_check(args),
+        cli::SeedGenCommand::Logic { subcommand } => logic::logic(subcommand),
         cli::SeedGenCommand::Regenerate { args } => seed::regenerate_seed(args),
     }
     .map_or_else(
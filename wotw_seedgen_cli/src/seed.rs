@@ -10,11 +10,12 @@ use std::io::{self, Read, Write};
 use std::path::PathBuf;
 use std::time::Instant;
 
+use itertools::Itertools;
 use log::LevelFilter;
 use serde::{Deserialize, Serialize};
 
 use wotw_seedgen::files::FILE_SYSTEM_ACCESS;
-use wotw_seedgen::generator::{Seed, SeedSpoiler};
+use wotw_seedgen::generator::{Seed, SeedSpoiler, SpoilerFormat};
 use wotw_seedgen::logic;
 use wotw_seedgen::settings;
 use wotw_seedgen::settings::UniverseSettings;
@@ -120,11 +121,17 @@ fn read_input_files(
 
 fn write_seeds(args: SeedMetaArgs, seed: Seed<'_, '_>) -> Result<(), String> {
     if args.tostdout {
-        write_seeds_to_stdout(seed, args.json)?;
+        write_seeds_to_stdout(seed, args.json, &args.spoiler_formats)?;
     } else {
         let filename = args.filename.unwrap_or_else(|| String::from("seed"));
 
-        write_seeds_to_files(&seed, &filename, args.seed_folder, args.json)?;
+        write_seeds_to_files(
+            &seed,
+            &filename,
+            args.seed_folder,
+            args.json,
+            &args.spoiler_formats,
+        )?;
     }
 
     if args.launch {
@@ -143,6 +150,7 @@ fn write_seeds_to_files(
     filename: &str,
     mut folder: PathBuf,
     json_spoiler: bool,
+    spoiler_formats: &[SpoilerFormat],
 ) -> Result<(), String> {
     let seeds = seed.seed_files()?;
     let multiworld = seeds.len() > 1;
@@ -179,23 +187,30 @@ fn write_seeds_to_files(
         }
     }
 
-    let mut path = folder;
-    path.push(format!("{filename}_spoiler"));
-
-    let contents = match json_spoiler {
-        true => {
-            path.set_extension("json");
-            seed.spoiler.to_json()
+    let default_format = match json_spoiler {
+        true => SpoilerFormat::Json,
+        false => SpoilerFormat::Text,
+    };
+    let mut formats = vec![default_format];
+    for &format in spoiler_formats {
+        if !formats.contains(&format) {
+            formats.push(format);
         }
-        false => {
-            path.set_extension("txt");
-            seed.spoiler.to_string()
+    }
+
+    for format in formats {
+        let mut path = folder.clone();
+        match format {
+            SpoilerFormat::ZoneSummary => path.push(format!("{filename}_zones")),
+            _ => path.push(format!("{filename}_spoiler")),
         }
-    };
+        path.set_extension(format.extension());
 
-    let file =
-        create_seedfile(path, &contents).map_err(|err| format!("Error writing spoiler: {err}"))?;
-    log::info!("Wrote spoiler to {}", file.display());
+        let contents = seed.spoiler.render(format);
+        let file = create_seedfile(path, &contents)
+            .map_err(|err| format!("Error writing spoiler: {err}"))?;
+        log::info!("Wrote spoiler to {}", file.display());
+    }
 
     Ok(())
 }
@@ -248,15 +263,34 @@ fn create_multiworld_folder(path: PathBuf) -> Result<PathBuf, io::Error> {
     }
 }
 
-fn write_seeds_to_stdout(seed: Seed, json: bool) -> Result<(), String> {
+fn write_seeds_to_stdout(
+    seed: Seed,
+    json: bool,
+    spoiler_formats: &[SpoilerFormat],
+) -> Result<(), String> {
     let files = seed.seed_files()?;
 
+    // The text spoiler is always included
+    let formats = spoiler_formats
+        .iter()
+        .copied()
+        .filter(|&format| format != SpoilerFormat::Text)
+        .unique();
+
     if json {
         let spoiler_text = seed.spoiler.to_string();
+        let rendered_spoilers = formats
+            .filter(|&format| format != SpoilerFormat::Json)
+            .map(|format| RenderedSpoiler {
+                format,
+                content: seed.spoiler.render(format),
+            })
+            .collect();
         let output = SeedgenCliJsonOutput {
             seed_files: files,
             spoiler: seed.spoiler,
             spoiler_text,
+            rendered_spoilers,
         };
 
         println!("{}", output.to_json())
@@ -273,6 +307,12 @@ fn write_seeds_to_stdout(seed: Seed, json: bool) -> Result<(), String> {
         println!();
         println!("======= Spoiler =======");
         println!("{}", seed.spoiler);
+
+        for format in formats {
+            println!();
+            println!("======= Spoiler ({format}) =======");
+            println!("{}", seed.spoiler.render(format));
+        }
     }
 
     Ok(())
@@ -288,6 +328,15 @@ struct SeedgenCliJsonOutput {
     pub spoiler: SeedSpoiler,
     /// Text representation of the spoiler
     pub spoiler_text: String,
+    /// Additional spoiler formats that were requested, other than text and json
+    pub rendered_spoilers: Vec<RenderedSpoiler>,
+}
+/// A spoiler rendered in one of the requested [`SpoilerFormat`]s
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RenderedSpoiler {
+    pub format: SpoilerFormat,
+    pub content: String,
 }
 
 impl SeedgenCliJsonOutput {
@@ -2,15 +2,18 @@ use std::fmt::Write;
 use std::{fs, path::PathBuf};
 
 use log::LevelFilter;
+use rustc_hash::FxHashSet;
 use sanitize_filename::sanitize;
+use wotw_seedgen::generator::SeedSpoiler;
+use wotw_seedgen::preset::UniversePreset;
 use wotw_seedgen::settings::Spawn;
 use wotw_seedgen::util::constants::DEFAULT_SPAWN;
 use wotw_seedgen::world::Graph;
 use wotw_seedgen::{files::FILE_SYSTEM_ACCESS, logic, settings::UniverseSettings};
 use wotw_seedgen_stats::analyzers::{self, Analyzer};
 use wotw_seedgen_stats::{
-    files::{FileAccess, FileSystemAccess},
-    StatsArgs,
+    files::{ArchiveAccess, FileAccess, FileSystemAccess},
+    CompareArgs, CompareSide, StatsArgs,
 };
 
 use crate::{cli, log_init};
@@ -29,6 +32,9 @@ pub fn generate_stats(args: cli::StatsArgs) -> Result<(), String> {
         tolerated_errors,
         error_message_limit,
         overwrite_cache,
+        seed_archive,
+        compare_presets,
+        compare_areas,
         settings: settings_args,
     } = args;
 
@@ -117,10 +123,20 @@ pub fn generate_stats(args: cli::StatsArgs) -> Result<(), String> {
                         box_analyzer(analyzers::LocationItemStats { location })
                     }
                     cli::Analyzer::Progression => box_analyzer(analyzers::ProgressionStats),
+                    cli::Analyzer::SeedLength { result_bucket_size } => {
+                        box_analyzer(analyzers::SeedLengthStats { result_bucket_size })
+                    }
                     cli::Analyzer::SpawnItemCount => box_analyzer(analyzers::SpawnItemCountStats),
                     cli::Analyzer::SpawnItems => box_analyzer(analyzers::SpawnItemStats),
                     cli::Analyzer::SpawnLocation => box_analyzer(analyzers::SpawnLocationStats),
                     cli::Analyzer::SpawnRegion => box_analyzer(analyzers::SpawnRegionStats),
+                    cli::Analyzer::SphereDepth {
+                        item,
+                        result_bucket_size,
+                    } => box_analyzer(analyzers::SphereDepthStats {
+                        item,
+                        result_bucket_size,
+                    }),
                     cli::Analyzer::StepSize { result_bucket_size } => {
                         box_analyzer(analyzers::StepSizeStats { result_bucket_size })
                     }
@@ -143,16 +159,84 @@ pub fn generate_stats(args: cli::StatsArgs) -> Result<(), String> {
         })
         .collect();
 
-    let args = StatsArgs {
-        settings,
-        sample_size,
-        analyzers,
-        graph: &graph,
-        tolerated_errors,
-        error_message_limit: Some(error_message_limit),
-        overwrite_seed_storage: overwrite_cache,
+    let results = if compare_presets.is_empty() && compare_areas.is_none() {
+        let args = StatsArgs {
+            settings,
+            sample_size,
+            analyzers,
+            graph: &graph,
+            tolerated_errors,
+            error_message_limit: Some(error_message_limit),
+            overwrite_seed_storage: overwrite_cache,
+        };
+        wotw_seedgen_stats::stats(args, &SeedStorage::new(seed_archive))?
+            .into_iter()
+            .map(|stats| (stats.title(), stats.csv()))
+            .collect::<Vec<_>>()
+    } else {
+        let mut candidate_settings = settings.clone();
+        if !compare_presets.is_empty() {
+            let preset = UniversePreset {
+                includes: Some(FxHashSet::from_iter(compare_presets)),
+                ..UniversePreset::default()
+            };
+            candidate_settings
+                .apply_preset(preset, &FILE_SYSTEM_ACCESS)
+                .map_err(|err| err.to_string())?;
+        }
+        let candidate_graph = match &compare_areas {
+            None => None,
+            Some(compare_areas) => {
+                let areas = fs::read_to_string(compare_areas).map_err(|err| {
+                    format!("Failed to read {}: {}", compare_areas.display(), err)
+                })?;
+                Some(logic::parse_logic(
+                    &areas,
+                    &locations,
+                    &states,
+                    &candidate_settings,
+                    false,
+                )?)
+            }
+        };
+
+        let mut candidate_settings_path = path.clone();
+        candidate_settings_path.push("candidate_settings.json");
+        fs::write(&candidate_settings_path, candidate_settings.to_json()).map_err(|err| {
+            format!(
+                "failed to write settings to \"{}\": {}",
+                candidate_settings_path.display(),
+                err
+            )
+        })?;
+
+        let args = CompareArgs {
+            baseline: CompareSide {
+                settings,
+                graph: &graph,
+                use_seed_storage: true,
+            },
+            candidate: CompareSide {
+                settings: candidate_settings,
+                graph: candidate_graph.as_ref().unwrap_or(&graph),
+                use_seed_storage: candidate_graph.is_none(),
+            },
+            sample_size,
+            analyzers,
+            tolerated_errors,
+            error_message_limit: Some(error_message_limit),
+            overwrite_seed_storage: overwrite_cache,
+        };
+        wotw_seedgen_stats::compare(args, &SeedStorage::new(seed_archive))?
+            .into_iter()
+            .map(|comparison| {
+                (
+                    format!("{} comparison", comparison.title()),
+                    comparison.csv(),
+                )
+            })
+            .collect()
     };
-    let stats = wotw_seedgen_stats::stats::<FileSystemAccess>(args)?;
 
     fs::create_dir_all(&path).map_err(|err| {
         format!(
@@ -161,10 +245,9 @@ pub fn generate_stats(args: cli::StatsArgs) -> Result<(), String> {
             err
         )
     })?; // It might've been a while ago that we created this folder, lets check if we need to recreate it in case the user deleted it in the meantime
-    for stats in stats {
-        let csv = stats.csv();
+    for (title, csv) in results {
         let mut path = path.clone();
-        path.push(format!("{}.csv", sanitize(stats.title())));
+        path.push(format!("{}.csv", sanitize(title)));
         fs::write(&path, csv).map_err(|err| {
             format!(
                 "failed to write statistics to \"{}\": {}",
@@ -187,11 +270,58 @@ pub fn generate_stats(args: cli::StatsArgs) -> Result<(), String> {
     Ok(())
 }
 
-pub fn clean_stats_cache() -> Result<(), String> {
+pub fn clean_stats_cache(seed_archive: Option<PathBuf>) -> Result<(), String> {
     log_init::initialize_log(None, LevelFilter::Warn, false)
         .unwrap_or_else(|err| eprintln!("Failed to initialize log: {err}"));
 
-    wotw_seedgen_stats::files::FileSystemAccess::clean_all_seeds()
+    SeedStorage::new(seed_archive).clean_all_seeds()
+}
+
+/// The seed cache selected through the command line
+enum SeedStorage {
+    FileSystem(FileSystemAccess),
+    Archive(ArchiveAccess),
+}
+impl SeedStorage {
+    fn new(seed_archive: Option<PathBuf>) -> Self {
+        match seed_archive {
+            None => SeedStorage::FileSystem(FileSystemAccess),
+            Some(path) => SeedStorage::Archive(ArchiveAccess::new(path)),
+        }
+    }
+}
+impl FileAccess for SeedStorage {
+    type Iter = Box<dyn Iterator<Item = Result<SeedSpoiler, String>>>;
+
+    fn read_seeds(&self, settings: &UniverseSettings, limit: usize) -> Result<Self::Iter, String> {
+        Ok(match self {
+            SeedStorage::FileSystem(access) => Box::new(access.read_seeds(settings, limit)?),
+            SeedStorage::Archive(access) => Box::new(access.read_seeds(settings, limit)?),
+        })
+    }
+    fn write_seed(
+        &self,
+        seed: &SeedSpoiler,
+        settings: &UniverseSettings,
+        key: usize,
+    ) -> Result<(), String> {
+        match self {
+            SeedStorage::FileSystem(access) => access.write_seed(seed, settings, key),
+            SeedStorage::Archive(access) => access.write_seed(seed, settings, key),
+        }
+    }
+    fn clean_seeds(&self, settings: &UniverseSettings) -> Result<(), String> {
+        match self {
+            SeedStorage::FileSystem(access) => access.clean_seeds(settings),
+            SeedStorage::Archive(access) => access.clean_seeds(settings),
+        }
+    }
+    fn clean_all_seeds(&self) -> Result<(), String> {
+        match self {
+            SeedStorage::FileSystem(access) => access.clean_all_seeds(),
+            SeedStorage::Archive(access) => access.clean_all_seeds(),
+        }
+    }
 }
 
 fn summarize_settings(settings: &UniverseSettings, graph: &Graph) -> String {
@@ -232,7 +362,7 @@ fn summarize_settings(settings: &UniverseSettings, graph: &Graph) -> String {
         summary.push_str("Glitches ")
     }
 
-    if all!(|world_settings| matches!(world_settings.spawn, Spawn::Random | Spawn::FullyRandom)) {
+    if all!(|world_settings| world_settings.is_random_spawn()) {
         if all!(|world_settings| matches!(world_settings.spawn, Spawn::FullyRandom)) {
             summary.push_str("Fully Random Spawn ")
         } else {
@@ -0,0 +1,306 @@
+//! Language server for the .wotwrh header language and .wotw logic files, communicating over stdio
+
+use std::{collections::HashMap, error::Error};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, CompletionResponse,
+    Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, Documentation, GotoDefinitionParams, GotoDefinitionResponse, Hover,
+    HoverContents, HoverParams, HoverProviderCapability, Location, MarkedString, OneOf, Position,
+    PublishDiagnosticsParams, Range, ReferenceParams, ServerCapabilities,
+    TextDocumentPositionParams, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+use serde::de::DeserializeOwned;
+use wotw_seedgen::{
+    header,
+    languages::{analysis::CompletionKind, ParseError},
+    logic,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error + Sync + Send>>;
+
+fn main() -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["!".to_string(), "(".to_string(), " ".to_string()]),
+            ..CompletionOptions::default()
+        }),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        ..ServerCapabilities::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    Server {
+        connection: &connection,
+        documents: HashMap::new(),
+    }
+    .run()?;
+
+    io_threads.join()?;
+    Ok(())
+}
+
+/// The language of a document, determined by its file extension
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Language {
+    Header,
+    Logic,
+}
+impl Language {
+    fn of(uri: &Url) -> Option<Language> {
+        match uri.path().rsplit_once('.')?.1 {
+            "wotwrh" => Some(Language::Header),
+            "wotw" => Some(Language::Logic),
+            _ => None,
+        }
+    }
+}
+
+struct Server<'a> {
+    connection: &'a Connection,
+    /// Contents of all open documents
+    documents: HashMap<Url, String>,
+}
+
+impl Server<'_> {
+    fn run(&mut self) -> Result<()> {
+        let connection = self.connection;
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        let result = match &request.method[..] {
+            "textDocument/hover" => params::<HoverParams>(request)
+                .and_then(|params| serde_json::to_value(self.hover(params))),
+            "textDocument/completion" => params::<CompletionParams>(request)
+                .and_then(|params| serde_json::to_value(self.completion(params))),
+            "textDocument/definition" => params::<GotoDefinitionParams>(request)
+                .and_then(|params| serde_json::to_value(self.definition(params))),
+            "textDocument/references" => params::<ReferenceParams>(request)
+                .and_then(|params| serde_json::to_value(self.references(params))),
+            _ => {
+                return Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("Unsupported request {}", request.method),
+                )
+            }
+        };
+
+        match result {
+            Ok(result) => Response::new_ok(id, result),
+            Err(err) => Response::new_err(id, ErrorCode::InvalidParams as i32, err.to_string()),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        match &notification.method[..] {
+            "textDocument/didOpen" => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                self.update(params.text_document.uri, params.text_document.text)?;
+            }
+            "textDocument/didChange" => {
+                let params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                // With full sync, the last change contains the whole document
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.update(params.text_document.uri, change.text)?;
+                }
+            }
+            "textDocument/didClose" => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                self.publish_diagnostics(params.text_document.uri, vec![])?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self, uri: Url, text: String) -> Result<()> {
+        let errors = match Language::of(&uri) {
+            Some(Language::Header) => header::analysis::diagnostics(&text),
+            Some(Language::Logic) => logic::analysis::diagnostics(&text),
+            None => Vec::<ParseError>::new(),
+        };
+        let diagnostics = errors
+            .into_iter()
+            .map(|error| {
+                let mut message = error.message;
+                if let Some(suggestion) = error.suggestion {
+                    message.push_str(&format!("\nExpected: {suggestion}"));
+                }
+                Diagnostic {
+                    range: range(&text, error.range),
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some("wotw_seedgen".to_string()),
+                    message,
+                    ..Diagnostic::default()
+                }
+            })
+            .collect();
+
+        self.documents.insert(uri.clone(), text);
+        self.publish_diagnostics(uri, diagnostics)
+    }
+
+    fn publish_diagnostics(&self, uri: Url, diagnostics: Vec<Diagnostic>) -> Result<()> {
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+        let notification = Notification::new("textDocument/publishDiagnostics".to_string(), params);
+        self.connection
+            .sender
+            .send(Message::Notification(notification))?;
+        Ok(())
+    }
+
+    /// Returns the language, document and byte offset referred to by `params`
+    fn document_offset(
+        &self,
+        params: &TextDocumentPositionParams,
+    ) -> Option<(Language, &str, usize)> {
+        let uri = &params.text_document.uri;
+        let language = Language::of(uri)?;
+        let text = self.documents.get(uri)?;
+        Some((language, text, offset(text, params.position)))
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let (language, text, offset) =
+            self.document_offset(&params.text_document_position_params)?;
+        let hover = match language {
+            Language::Header => header::analysis::hover(text, offset),
+            Language::Logic => logic::analysis::hover(text, offset),
+        };
+        hover.map(|hover| Hover {
+            contents: HoverContents::Scalar(MarkedString::String(hover.text)),
+            range: Some(range(text, hover.range)),
+        })
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let (language, text, offset) = self.document_offset(&params.text_document_position)?;
+        let completions = match language {
+            Language::Header => header::analysis::completions(text, offset),
+            Language::Logic => logic::analysis::completions(text, offset),
+        };
+        let items = completions
+            .into_iter()
+            .map(|completion| CompletionItem {
+                label: completion.label,
+                kind: Some(match completion.kind {
+                    CompletionKind::Command => CompletionItemKind::KEYWORD,
+                    CompletionKind::Parameter => CompletionItemKind::VARIABLE,
+                    CompletionKind::Skill | CompletionKind::Trick => CompletionItemKind::CONSTANT,
+                    CompletionKind::Requirement => CompletionItemKind::FUNCTION,
+                    CompletionKind::Anchor => CompletionItemKind::MODULE,
+                }),
+                detail: completion.detail,
+                documentation: completion.documentation.map(Documentation::String),
+                ..CompletionItem::default()
+            })
+            .collect();
+        Some(CompletionResponse::Array(items))
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let (language, text, offset) =
+            self.document_offset(&params.text_document_position_params)?;
+        if language != Language::Header {
+            return None;
+        }
+        let include = header::analysis::include_at(text, offset)?;
+        let target = resolve_include(uri, include.name)?;
+        Some(GotoDefinitionResponse::Scalar(Location::new(
+            target,
+            Range::default(),
+        )))
+    }
+
+    fn references(&self, params: ReferenceParams) -> Option<Vec<Location>> {
+        let uri = &params.text_document_position.text_document.uri;
+        let (language, text, offset) = self.document_offset(&params.text_document_position)?;
+        if language != Language::Logic {
+            return None;
+        }
+        let references =
+            logic::analysis::references(text, offset, params.context.include_declaration)
+                .into_iter()
+                .map(|reference| Location::new(uri.clone(), range(text, reference)))
+                .collect();
+        Some(references)
+    }
+}
+
+fn params<P: DeserializeOwned>(request: Request) -> serde_json::Result<P> {
+    serde_json::from_value(request.params)
+}
+
+/// Finds the file of an included header next to the including header or in its "headers" subdirectory
+fn resolve_include(uri: &Url, name: &str) -> Option<Url> {
+    let path = uri.to_file_path().ok()?;
+    let directory = path.parent()?;
+    let file_name = format!("{name}.wotwrh");
+    [
+        directory.join(&file_name),
+        directory.join("headers").join(&file_name),
+    ]
+    .into_iter()
+    .find(|path| path.is_file())
+    .and_then(|path| Url::from_file_path(path).ok())
+}
+
+/// Converts an lsp [`Position`], which counts characters in utf-16, to a byte offset
+fn offset(text: &str, position: Position) -> usize {
+    let line_start = text
+        .split_inclusive('\n')
+        .take(position.line as usize)
+        .map(str::len)
+        .sum::<usize>();
+
+    let mut character = 0;
+    for (index, c) in text[line_start..].char_indices() {
+        if character >= position.character as usize || c == '\n' {
+            return line_start + index;
+        }
+        character += c.len_utf16();
+    }
+    text.len()
+}
+
+/// Converts a byte offset to an lsp [`Position`]
+fn position(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let character = before[line_start..].encode_utf16().count();
+    Position::new(line as u32, character as u32)
+}
+
+fn range(text: &str, range: std::ops::Range<usize>) -> Range {
+    Range::new(position(text, range.start), position(text, range.end))
+}
@@ -6,10 +6,12 @@ mod item_unlock;
 mod item_zone;
 mod location_item;
 mod progression;
+mod seed_length;
 mod spawn_item_count;
 mod spawn_items;
 mod spawn_location;
 mod spawn_region;
+mod sphere_depth;
 mod step_size;
 mod zone_spirit_light;
 mod zone_unlock;
@@ -22,10 +24,12 @@ pub use item_unlock::ItemUnlockStats;
 pub use item_zone::ItemZoneStats;
 pub use location_item::LocationItemStats;
 pub use progression::ProgressionStats;
+pub use seed_length::SeedLengthStats;
 pub use spawn_item_count::SpawnItemCountStats;
 pub use spawn_items::SpawnItemStats;
 pub use spawn_location::SpawnLocationStats;
 pub use spawn_region::SpawnRegionStats;
+pub use sphere_depth::SphereDepthStats;
 pub use step_size::StepSizeStats;
 pub use zone_spirit_light::ZoneSpiritLightStats;
 pub use zone_unlock::ZoneUnlockStats;
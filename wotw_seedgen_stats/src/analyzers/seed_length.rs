@@ -0,0 +1,28 @@
+use std::num::NonZeroUsize;
+
+use wotw_seedgen::generator::SeedSpoiler;
+
+use super::Analyzer;
+
+/// Analyzes how many steps of progression a seed takes
+///
+/// Each step of the spoiler that made new locations reachable counts as one sphere
+pub struct SeedLengthStats {
+    /// How many adjacent result to group together
+    pub result_bucket_size: NonZeroUsize,
+}
+impl Analyzer for SeedLengthStats {
+    fn title(&self) -> String {
+        "Seed length in spheres".to_string()
+    }
+
+    fn analyze(&self, seed: &SeedSpoiler) -> Vec<String> {
+        let spheres = seed
+            .groups
+            .iter()
+            .filter(|group| !group.reachable.is_empty())
+            .count();
+
+        vec![super::group_result(spheres, self.result_bucket_size)]
+    }
+}
@@ -0,0 +1,40 @@
+use std::num::NonZeroUsize;
+
+use wotw_seedgen::generator::SeedSpoiler;
+
+use super::Analyzer;
+
+/// Analyzes in which sphere an item is placed
+///
+/// Spheres are counted like in [`SeedLengthStats`](super::SeedLengthStats), placements made before anything was reachable are in sphere 0
+///
+/// Every placement of the item counts separately, so items placed multiple times will add multiple results per seed
+pub struct SphereDepthStats {
+    pub item: String,
+    /// How many adjacent result to group together
+    pub result_bucket_size: NonZeroUsize,
+}
+impl Analyzer for SphereDepthStats {
+    fn title(&self) -> String {
+        format!("Sphere depth of {}", self.item)
+    }
+
+    fn analyze(&self, seed: &SeedSpoiler) -> Vec<String> {
+        seed.groups
+            .iter()
+            .scan(0, |depth, group| {
+                if !group.reachable.is_empty() {
+                    *depth += 1;
+                }
+                Some((*depth, group))
+            })
+            .flat_map(|(depth, group)| {
+                group
+                    .placements
+                    .iter()
+                    .filter(|placement| placement.item_name == self.item)
+                    .map(move |_| super::group_result(depth, self.result_bucket_size))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,421 @@
+use std::{f64::consts::PI, fmt::Write, sync::Arc, time::Instant};
+
+use itertools::Itertools;
+use rustc_hash::{FxHashMap, FxHashSet};
+use wotw_seedgen::{settings::UniverseSettings, world::Graph};
+
+use crate::{
+    compare_key_lists,
+    files::{FileAccess, NoFileAccess},
+    seed_storage::{self, SeedData},
+    ChainedAnalyzers, Result,
+};
+
+/// Arguments passed to [`compare`]
+pub struct CompareArgs<'graph> {
+    /// The settings and logic to compare against
+    pub baseline: CompareSide<'graph>,
+    /// The settings and logic that might shift the statistics
+    pub candidate: CompareSide<'graph>,
+    /// How many seeds to analyze on each side
+    pub sample_size: usize,
+    /// Any number of [`Analyzer`](crate::analyzers::Analyzer)s that will analyze the seeds of both sides
+    ///
+    /// See [`StatsArgs::analyzers`](crate::StatsArgs::analyzers) for how chaining works
+    pub analyzers: Vec<ChainedAnalyzers>,
+    /// How many errors during seed generation should be tolerated on each side before aborting
+    ///
+    /// If `None`, this will default to a value based on `sample_size`
+    pub tolerated_errors: Option<usize>,
+    /// How many error messages should be displayed after aborting due to `tolerated_errors` being exceeded
+    ///
+    /// If `None`, defaults to 10
+    pub error_message_limit: Option<usize>,
+    /// If `true`, cleans the seed storage for the settings of both sides and generates new seeds from scratch
+    pub overwrite_seed_storage: bool,
+}
+/// One of the two configurations compared by [`compare`]
+pub struct CompareSide<'graph> {
+    /// The [`UniverseSettings`] to generate seeds with
+    pub settings: UniverseSettings,
+    /// The logical [`Graph`]
+    pub graph: &'graph Graph,
+    /// Whether seeds may be reused from and written to the seed storage
+    ///
+    /// Stored seeds are only identified by their settings, so this should be `false` if `graph` was parsed from different logic files than the stored seeds
+    pub use_seed_storage: bool,
+}
+
+/// Statistics of the same [`Analyzer`](crate::analyzers::Analyzer)s for two configurations side by side
+pub struct Comparison {
+    analyzers: ChainedAnalyzers,
+    pub baseline: FxHashMap<Vec<Arc<String>>, u32>,
+    pub candidate: FxHashMap<Vec<Arc<String>>, u32>,
+}
+/// Result of Pearson's chi-squared test
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquared {
+    pub statistic: f64,
+    pub degrees_of_freedom: usize,
+    /// How likely a difference at least this large would be if both sides followed the same distribution
+    pub p_value: f64,
+}
+
+impl Comparison {
+    pub fn title(&self) -> String {
+        self.analyzers
+            .iter()
+            .map(|analyzer| analyzer.title())
+            .join(" and ")
+    }
+
+    /// Tests whether the baseline and candidate follow the same distribution across all keys
+    pub fn chi_squared(&self) -> ChiSquared {
+        let (baseline_total, candidate_total) = self.totals();
+        let keys = self.keys();
+
+        let statistic = keys
+            .iter()
+            .map(|key| {
+                let (baseline, candidate) = self.counts(key);
+                homogeneity_contribution(baseline, candidate, baseline_total, candidate_total)
+            })
+            .sum();
+        let degrees_of_freedom = keys.len().saturating_sub(1);
+
+        ChiSquared {
+            statistic,
+            degrees_of_freedom,
+            p_value: chi_squared_p_value(statistic, degrees_of_freedom),
+        }
+    }
+
+    /// Lists the counts and shares of each key for both sides, along with how significant the difference in shares is
+    ///
+    /// The p-value of each key tests that key against all other keys combined, the test across all keys is appended below the table
+    pub fn csv(&self) -> String {
+        let mut csv = self.title();
+        csv.push_str(", Baseline, Candidate, Baseline %, Candidate %, Delta %, p-value\n");
+
+        let (baseline_total, candidate_total) = self.totals();
+        let share = |count: u32, total: u32| {
+            if total == 0 {
+                0.
+            } else {
+                f64::from(count) * 100. / f64::from(total)
+            }
+        };
+
+        let mut keys = self.keys();
+        keys.sort_unstable_by(|a, b| compare_key_lists(&self.analyzers, a, b));
+
+        for key in keys {
+            let (baseline, candidate) = self.counts(key);
+            let baseline_share = share(baseline, baseline_total);
+            let candidate_share = share(candidate, candidate_total);
+            let p_value = key_p_value(baseline, candidate, baseline_total, candidate_total);
+
+            writeln!(
+                csv,
+                "{}, {baseline}, {candidate}, {baseline_share:.2}, {candidate_share:.2}, {:+.2}, {p_value:.4}",
+                key.iter().join(", "),
+                candidate_share - baseline_share,
+            )
+            .unwrap();
+        }
+
+        let ChiSquared {
+            statistic,
+            degrees_of_freedom,
+            p_value,
+        } = self.chi_squared();
+        write!(
+            csv,
+            "\nChi-squared, {statistic:.4}\nDegrees of freedom, {degrees_of_freedom}\np-value, {p_value:.4}"
+        )
+        .unwrap();
+
+        csv
+    }
+
+    fn keys(&self) -> Vec<&Vec<Arc<String>>> {
+        self.baseline
+            .keys()
+            .chain(self.candidate.keys())
+            .collect::<FxHashSet<_>>()
+            .into_iter()
+            .collect()
+    }
+    fn counts(&self, key: &[Arc<String>]) -> (u32, u32) {
+        (
+            self.baseline.get(key).copied().unwrap_or_default(),
+            self.candidate.get(key).copied().unwrap_or_default(),
+        )
+    }
+    fn totals(&self) -> (u32, u32) {
+        (self.baseline.values().sum(), self.candidate.values().sum())
+    }
+}
+
+/// Generates the same set of stats for two configurations and compares them
+///
+/// See [`CompareArgs`] for more details on the passed arguments
+pub fn compare(args: CompareArgs, file_access: &impl FileAccess) -> Result<Vec<Comparison>> {
+    let now = Instant::now();
+
+    let CompareArgs {
+        baseline,
+        candidate,
+        sample_size,
+        analyzers,
+        tolerated_errors,
+        error_message_limit,
+        overwrite_seed_storage,
+    } = args;
+
+    if baseline.settings.world_count() > 1 || candidate.settings.world_count() > 1 {
+        return Err("Multiworld seeds aren't well supported yet".to_string());
+    }
+
+    let analyze_side = |name: &str, side: &CompareSide| -> Result<Vec<SeedData>> {
+        eprintln!("Analyzing {name} seeds");
+
+        if side.use_seed_storage {
+            if overwrite_seed_storage {
+                file_access.clean_seeds(&side.settings)?;
+                eprintln!("Cleaned seed storage for the {name} settings");
+            }
+
+            seed_storage::analyze(
+                file_access,
+                &analyzers,
+                &side.settings,
+                sample_size,
+                tolerated_errors,
+                error_message_limit,
+                side.graph,
+            )
+        } else {
+            seed_storage::analyze(
+                &NoFileAccess,
+                &analyzers,
+                &side.settings,
+                sample_size,
+                tolerated_errors,
+                error_message_limit,
+                side.graph,
+            )
+        }
+    };
+    let baseline_data = analyze_side("baseline", &baseline)?;
+    let candidate_data = analyze_side("candidate", &candidate)?;
+
+    let comparisons = baseline_data
+        .into_iter()
+        .zip(candidate_data)
+        .zip(analyzers)
+        .map(|((baseline, candidate), analyzers)| Comparison {
+            analyzers,
+            baseline,
+            candidate,
+        })
+        .collect();
+
+    let elapsed = now.elapsed();
+    eprintln!("Generated comparison in {:.1}s", elapsed.as_secs_f32());
+
+    Ok(comparisons)
+}
+
+/// Contribution of one key to the chi-squared statistic of the test of homogeneity
+fn homogeneity_contribution(
+    baseline: u32,
+    candidate: u32,
+    baseline_total: u32,
+    candidate_total: u32,
+) -> f64 {
+    let total = f64::from(baseline_total) + f64::from(candidate_total);
+    let key_total = f64::from(baseline) + f64::from(candidate);
+    if total == 0. || key_total == 0. {
+        return 0.;
+    }
+
+    [(baseline, baseline_total), (candidate, candidate_total)]
+        .into_iter()
+        .map(|(count, side_total)| {
+            let expected = f64::from(side_total) * key_total / total;
+            if expected == 0. {
+                0.
+            } else {
+                (f64::from(count) - expected).powi(2) / expected
+            }
+        })
+        .sum()
+}
+
+/// p-value of the chi-squared test comparing one key against all other keys combined
+fn key_p_value(baseline: u32, candidate: u32, baseline_total: u32, candidate_total: u32) -> f64 {
+    let (a, b) = (f64::from(baseline), f64::from(candidate));
+    let (a_total, b_total) = (f64::from(baseline_total), f64::from(candidate_total));
+    let total = a_total + b_total;
+    let key_total = a + b;
+
+    let denominator = a_total * b_total * key_total * (total - key_total);
+    if denominator == 0. {
+        return 1.;
+    }
+
+    let statistic = total * (a * (b_total - b) - b * (a_total - a)).powi(2) / denominator;
+    chi_squared_p_value(statistic, 1)
+}
+
+/// Probability of a chi-squared distributed value being at least `statistic`
+fn chi_squared_p_value(statistic: f64, degrees_of_freedom: usize) -> f64 {
+    if degrees_of_freedom == 0 || statistic <= 0. {
+        return 1.;
+    }
+
+    upper_regularized_gamma(degrees_of_freedom as f64 / 2., statistic / 2.).clamp(0., 1.)
+}
+
+/// The regularized upper incomplete gamma function Q(a, x)
+fn upper_regularized_gamma(a: f64, x: f64) -> f64 {
+    const ITERATIONS: usize = 500;
+    const EPSILON: f64 = 1e-12;
+    const TINY: f64 = 1e-300;
+
+    let prefactor = (a * x.ln() - x - ln_gamma(a)).exp();
+
+    if x < a + 1. {
+        // The series converges quickly for small x, it yields the lower function P(a, x) = 1 - Q(a, x)
+        let mut term = 1. / a;
+        let mut sum = term;
+        for n in 1..ITERATIONS {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+
+        1. - sum * prefactor
+    } else {
+        // Continued fraction using the modified Lentz method
+        let mut b = x + 1. - a;
+        let mut c = 1. / TINY;
+        let mut d = 1. / b;
+        let mut fraction = d;
+        for n in 1..ITERATIONS {
+            let n = n as f64;
+            let an = -n * (n - a);
+            b += 2.;
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1. / d;
+            let delta = d * c;
+            fraction *= delta;
+            if (delta - 1.).abs() < EPSILON {
+                break;
+            }
+        }
+
+        prefactor * fraction
+    }
+}
+
+/// Natural logarithm of the gamma function, using the Lanczos approximation
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.001_208_650_973_866_179,
+        -0.000_005_395_239_384_953,
+    ];
+
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let series = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .fold(1.000_000_000_190_015, |series, (index, coefficient)| {
+            series + coefficient / (x + 1. + index as f64)
+        });
+
+    -tmp + ((2. * PI).sqrt() * series / x).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::SpawnLocationStats;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() < tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn gamma_functions() {
+        assert_close(ln_gamma(1.), 0., 1e-10);
+        assert_close(ln_gamma(5.), 24_f64.ln(), 1e-10);
+        assert_close(ln_gamma(0.5), PI.sqrt().ln(), 1e-10);
+
+        // Q(1, x) = e^-x, covering both the series and the continued fraction
+        for x in [0.1, 0.5, 1.5, 3., 10.] {
+            assert_close(upper_regularized_gamma(1., x), (-x).exp(), 1e-9);
+        }
+    }
+
+    #[test]
+    fn chi_squared_p_values() {
+        assert_close(chi_squared_p_value(3.841, 1), 0.05, 1e-3);
+        assert_close(chi_squared_p_value(5.991, 2), 0.05, 1e-3);
+        assert_close(chi_squared_p_value(6.635, 1), 0.01, 1e-3);
+        assert_eq!(chi_squared_p_value(0., 3), 1.);
+        assert_eq!(chi_squared_p_value(10., 0), 1.);
+
+        assert_eq!(key_p_value(5, 10, 50, 100), 1.);
+        assert_eq!(key_p_value(0, 0, 50, 100), 1.);
+        assert_close(
+            key_p_value(10, 0, 10, 10),
+            chi_squared_p_value(20., 1),
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn comparison_csv() {
+        let key = |key: &str| vec![Arc::new(key.to_string())];
+        let comparison = Comparison {
+            analyzers: vec![Box::new(SpawnLocationStats)],
+            baseline: [(key("A"), 10)].into_iter().collect(),
+            candidate: [(key("B"), 10)].into_iter().collect(),
+        };
+
+        let chi_squared = comparison.chi_squared();
+        assert_close(chi_squared.statistic, 20., 1e-12);
+        assert_eq!(chi_squared.degrees_of_freedom, 1);
+
+        assert_eq!(
+            comparison.csv(),
+            "\
+Spawn Location, Baseline, Candidate, Baseline %, Candidate %, Delta %, p-value
+A, 10, 0, 100.00, 0.00, -100.00, 0.0000
+B, 0, 10, 0.00, 100.00, +100.00, 0.0000
+
+Chi-squared, 20.0000
+Degrees of freedom, 1
+p-value, 0.0000"
+        );
+    }
+}
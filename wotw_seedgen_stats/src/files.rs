@@ -1,5 +1,6 @@
-use std::iter;
+use std::{iter, sync::Mutex, vec};
 
+use rustc_hash::FxHashMap;
 use wotw_seedgen::{generator::SeedSpoiler, settings::UniverseSettings};
 
 use crate::Result;
@@ -11,20 +12,23 @@ use crate::Result;
 ///
 /// You can work without this behaviour by using [`NoFileAccess`], generating all seeds frmo scratch every time
 ///
-/// If the `fs` feature is enabled, you may use [`FileSystemAccess`] to store seeds in the local filesystem
-pub trait FileAccess {
+/// If the `fs` feature is enabled, you may use [`FileSystemAccess`] to store seeds in the local filesystem, or [`ArchiveAccess`] to store them in a single file
+///
+/// Seeds are written from multiple threads at once, so implementations have to synchronize their writes
+pub trait FileAccess: Sync {
     type Iter: Iterator<Item = Result<SeedSpoiler>>;
 
     /// fetch seeds that have been previously generated with these settings
-    fn read_seeds(settings: &UniverseSettings, limit: usize) -> Result<Self::Iter>;
+    fn read_seeds(&self, settings: &UniverseSettings, limit: usize) -> Result<Self::Iter>;
     /// write a seed generated from these settings for later use
     ///
     /// `key` should be unique, although it is recommended you don't rely on this being true and take it as a hint for what key you could use
-    fn write_seed(seed: &SeedSpoiler, settings: &UniverseSettings, key: usize) -> Result<()>;
+    fn write_seed(&self, seed: &SeedSpoiler, settings: &UniverseSettings, key: usize)
+        -> Result<()>;
     /// clean all seeds that have previously been generated with these settings
-    fn clean_seeds(settings: &UniverseSettings) -> Result<()>;
+    fn clean_seeds(&self, settings: &UniverseSettings) -> Result<()>;
     /// clean all seeds that have previously been generated
-    fn clean_all_seeds() -> Result<()>;
+    fn clean_all_seeds(&self) -> Result<()>;
 }
 
 /// A [`FileAccess`] implementation prohibiting access to any files
@@ -34,20 +38,78 @@ pub struct NoFileAccess;
 impl FileAccess for NoFileAccess {
     type Iter = iter::Empty<Result<SeedSpoiler>>;
 
-    fn read_seeds(_: &UniverseSettings, _: usize) -> Result<Self::Iter> {
+    fn read_seeds(&self, _: &UniverseSettings, _: usize) -> Result<Self::Iter> {
         Ok(iter::empty())
     }
-    fn write_seed(_: &SeedSpoiler, _: &UniverseSettings, _: usize) -> Result<()> {
+    fn write_seed(&self, _: &SeedSpoiler, _: &UniverseSettings, _: usize) -> Result<()> {
+        Ok(())
+    }
+    fn clean_seeds(&self, _: &UniverseSettings) -> Result<()> {
+        Ok(())
+    }
+    fn clean_all_seeds(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A [`FileAccess`] implementation keeping seeds in memory
+///
+/// Stored seeds are lost once this is dropped, which mostly makes it useful for tests
+#[derive(Default)]
+pub struct MemoryAccess {
+    seeds: Mutex<FxHashMap<u64, Vec<SeedSpoiler>>>,
+}
+impl FileAccess for MemoryAccess {
+    type Iter = vec::IntoIter<Result<SeedSpoiler>>;
+
+    fn read_seeds(&self, settings: &UniverseSettings, limit: usize) -> Result<Self::Iter> {
+        let seeds = self.seeds.lock().expect(POISONED);
+        let seeds = seeds
+            .get(&hash_settings(settings))
+            .map_or_else(Vec::new, |seeds| {
+                seeds.iter().take(limit).cloned().map(Ok).collect()
+            });
+
+        Ok(seeds.into_iter())
+    }
+    fn write_seed(&self, seed: &SeedSpoiler, settings: &UniverseSettings, _: usize) -> Result<()> {
+        self.seeds
+            .lock()
+            .expect(POISONED)
+            .entry(hash_settings(settings))
+            .or_default()
+            .push(seed.clone());
         Ok(())
     }
-    fn clean_seeds(_: &UniverseSettings) -> Result<()> {
+    fn clean_seeds(&self, settings: &UniverseSettings) -> Result<()> {
+        self.seeds
+            .lock()
+            .expect(POISONED)
+            .remove(&hash_settings(settings));
         Ok(())
     }
-    fn clean_all_seeds() -> Result<()> {
+    fn clean_all_seeds(&self) -> Result<()> {
+        self.seeds.lock().expect(POISONED).clear();
         Ok(())
     }
 }
 
+const POISONED: &str = "seed storage was poisoned";
+
+/// Identifies the settings seeds were generated with
+///
+/// Stable across runs and machines, so stored seeds can be shared:
+/// this is a 64-bit FNV-1a hash over the bincode serialization, which uses fixed-width little-endian integers
+fn hash_settings(settings: &UniverseSettings) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+    let bytes = bincode::serialize(&settings.world_settings).expect("Failed to serialize settings"); // We deliberately ignore the seed
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
 #[cfg(feature = "fs")]
 pub use fs_access::*;
 #[cfg(feature = "fs")]
@@ -58,13 +120,10 @@ mod fs_access {
 
     use std::{
         fs::{self, DirEntry, ReadDir},
-        hash::{Hash, Hasher},
         io::{self, Write},
         path::{Path, PathBuf},
     };
 
-    use rustc_hash::FxHasher;
-
     const SEED_STORAGE_FOLDER: &str = "seed_storage";
 
     /// A [`FileAccess`] implementation storing and fetching seeds using the local filesystem
@@ -72,13 +131,14 @@ mod fs_access {
     impl FileAccess for FileSystemAccess {
         type Iter = ReadSeeds;
 
-        fn read_seeds(settings: &UniverseSettings, limit: usize) -> Result<Self::Iter> {
+        fn read_seeds(&self, settings: &UniverseSettings, limit: usize) -> Result<Self::Iter> {
             let path = path_from_settings(settings);
 
             ReadSeeds::new(path, limit)
         }
 
         fn write_seed(
+            &self,
             seed: &SeedSpoiler,
             settings: &UniverseSettings,
             mut key: usize,
@@ -111,14 +171,14 @@ mod fs_access {
                 }
             }
         }
-        fn clean_seeds(settings: &UniverseSettings) -> Result<()> {
+        fn clean_seeds(&self, settings: &UniverseSettings) -> Result<()> {
             let path = path_from_settings(settings);
             fs::remove_dir_all(path).or_else(|err| match err.kind() {
                 io::ErrorKind::NotFound => Ok(()),
                 _ => Err(format!("Failed to clean seed storage: {err}")),
             })
         }
-        fn clean_all_seeds() -> Result<()> {
+        fn clean_all_seeds(&self) -> Result<()> {
             fs::remove_dir_all(SEED_STORAGE_FOLDER).or_else(|err| match err.kind() {
                 io::ErrorKind::NotFound => Ok(()),
                 _ => Err(format!("Failed to clean seed storage: {err}")),
@@ -228,13 +288,6 @@ mod fs_access {
         path.push(folder);
         path
     }
-    fn hash_settings(settings: &UniverseSettings) -> u64 {
-        let mut hasher = FxHasher::default();
-        let bytes =
-            bincode::serialize(&settings.world_settings).expect("Failed to serialize settings"); // We deliberately ignore the seed
-        bytes.hash(&mut hasher);
-        hasher.finish()
-    }
     fn print_feedback_for_existing_seeds(seeds: HandleErrorsReadDir) {
         let modify_timestamps = HandleErrors::new_print_errors(
             HandleErrors::new_print_errors(seeds.map(|entry| {
@@ -279,3 +332,271 @@ mod fs_access {
         }
     }
 }
+
+#[cfg(feature = "fs")]
+pub use archive_access::*;
+#[cfg(feature = "fs")]
+mod archive_access {
+    use super::*;
+
+    use std::{
+        fs::{self, File},
+        io::{self, BufReader, Read, Seek, SeekFrom, Write},
+        path::PathBuf,
+    };
+
+    /// Size of the settings hash and seed length preceding every seed in the archive
+    const HEADER_SIZE: usize = 12;
+    /// Seed length marking that all previous seeds with the same settings hash have been cleaned
+    const CLEANED: u32 = 0;
+
+    /// A [`FileAccess`] implementation storing and fetching seeds using a single archive file
+    ///
+    /// Unlike [`FileSystemAccess`], this needs only one file no matter how many seeds are stored, which also makes it easy to share stored seeds between machines
+    ///
+    /// Seeds are only ever appended to the archive, cleaning seeds appends a marker that hides all previous seeds with the same settings
+    pub struct ArchiveAccess {
+        path: PathBuf,
+        lock: Mutex<()>,
+    }
+    impl ArchiveAccess {
+        /// Uses the archive at `path`, which will be created once the first seed is written
+        pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+            Self {
+                path: path.into(),
+                lock: Mutex::default(),
+            }
+        }
+
+        fn append(&self, settings: &UniverseSettings, bytes: &[u8]) -> Result<()> {
+            let length = u32::try_from(bytes.len())
+                .map_err(|_| "Seed is too large to store in the archive".to_string())?;
+
+            let mut record = Vec::with_capacity(HEADER_SIZE + bytes.len());
+            record.extend_from_slice(&hash_settings(settings).to_le_bytes());
+            record.extend_from_slice(&length.to_le_bytes());
+            record.extend_from_slice(bytes);
+
+            let _guard = self.lock.lock().expect(POISONED);
+            if let Some(parent) = self.path.parent() {
+                if !parent.as_os_str().is_empty() {
+                    fs::create_dir_all(parent).map_err(|err| {
+                        format!("Failed to create folder for seed archive: {err}")
+                    })?;
+                }
+            }
+            fs::OpenOptions::new()
+                .append(true)
+                .create(true)
+                .open(&self.path)
+                .and_then(|mut file| file.write_all(&record))
+                .map_err(|err| format!("Failed to write to seed archive: {err}"))
+        }
+    }
+    impl FileAccess for ArchiveAccess {
+        type Iter = ArchiveSeeds;
+
+        fn read_seeds(&self, settings: &UniverseSettings, limit: usize) -> Result<Self::Iter> {
+            let format_err = |err: io::Error| {
+                format!(
+                    "Failed to read seed archive at \"{}\": {}",
+                    self.path.display(),
+                    err
+                )
+            };
+
+            let _guard = self.lock.lock().expect(POISONED);
+            let file = match File::open(&self.path) {
+                Ok(file) => file,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    return Ok(ArchiveSeeds {
+                        file: None,
+                        records: Vec::new().into_iter(),
+                    })
+                }
+                Err(err) => return Err(format_err(err)),
+            };
+
+            let hash = hash_settings(settings);
+            let mut reader = BufReader::new(file);
+            let mut records = vec![];
+            let mut offset = 0;
+
+            loop {
+                let mut header = [0; HEADER_SIZE];
+                match reader.read_exact(&mut header) {
+                    Ok(()) => {}
+                    Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => break,
+                    Err(err) => return Err(format_err(err)),
+                }
+                let (record_hash, length) = header.split_at(8);
+                let record_hash = u64::from_le_bytes(record_hash.try_into().unwrap());
+                let length = u32::from_le_bytes(length.try_into().unwrap());
+                offset += HEADER_SIZE as u64;
+
+                if record_hash == hash {
+                    if length == CLEANED {
+                        records.clear();
+                    } else {
+                        records.push((offset, length));
+                    }
+                }
+
+                reader
+                    .seek_relative(i64::from(length))
+                    .map_err(format_err)?;
+                offset += u64::from(length);
+            }
+
+            records.truncate(limit);
+            if !records.is_empty() {
+                let amount = records.len();
+                eprintln!(
+                    "Reusing {} seed{} with these settings from \"{}\"",
+                    amount,
+                    if amount == 1 { "" } else { "s" },
+                    self.path.display()
+                );
+            }
+
+            Ok(ArchiveSeeds {
+                file: Some(reader.into_inner()),
+                records: records.into_iter(),
+            })
+        }
+        fn write_seed(
+            &self,
+            seed: &SeedSpoiler,
+            settings: &UniverseSettings,
+            _: usize,
+        ) -> Result<()> {
+            let bytes = bincode::serialize(seed).expect("Failed to serialize spoiler");
+            self.append(settings, &bytes)
+        }
+        fn clean_seeds(&self, settings: &UniverseSettings) -> Result<()> {
+            if self.path.exists() {
+                self.append(settings, &[])
+            } else {
+                Ok(())
+            }
+        }
+        fn clean_all_seeds(&self) -> Result<()> {
+            let _guard = self.lock.lock().expect(POISONED);
+            fs::remove_file(&self.path).or_else(|err| match err.kind() {
+                io::ErrorKind::NotFound => Ok(()),
+                _ => Err(format!("Failed to clean seed archive: {err}")),
+            })
+        }
+    }
+
+    /// An Iterator reading stored seeds from a seed archive
+    pub struct ArchiveSeeds {
+        file: Option<File>,
+        /// Offset and length of each seed to read
+        records: vec::IntoIter<(u64, u32)>,
+    }
+    impl Iterator for ArchiveSeeds {
+        type Item = Result<SeedSpoiler>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let (offset, length) = self.records.next()?;
+            let file = self.file.as_mut()?;
+
+            let mut bytes = vec![0; length as usize];
+            let result = file
+                .seek(SeekFrom::Start(offset))
+                .and_then(|_| file.read_exact(&mut bytes))
+                .map_err(|err| format!("Failed to read seed from seed archive: {err}"))
+                .and_then(|()| {
+                    bincode::deserialize(&bytes).map_err(|err| {
+                        format!("Failed to deserialize seed from seed archive: {err}")
+                    })
+                });
+
+            Some(result)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seed(spawn: &str) -> SeedSpoiler {
+        SeedSpoiler {
+            spawns: vec![spawn.to_string()],
+            groups: vec![],
+            required: vec![],
+            doors: vec![],
+        }
+    }
+
+    fn stored_spawns(file_access: &impl FileAccess, settings: &UniverseSettings) -> Vec<String> {
+        file_access
+            .read_seeds(settings, 10)
+            .unwrap()
+            .map(|seed| seed.unwrap().spawns.remove(0))
+            .collect()
+    }
+
+    fn check_storage(file_access: &impl FileAccess) {
+        let settings = UniverseSettings::default();
+        let mut other_settings = UniverseSettings::default();
+        other_settings.world_settings[0].hard = true;
+
+        file_access.clean_all_seeds().unwrap();
+        file_access.write_seed(&seed("A"), &settings, 0).unwrap();
+        file_access
+            .write_seed(&seed("B"), &other_settings, 0)
+            .unwrap();
+        file_access.write_seed(&seed("C"), &settings, 1).unwrap();
+        assert_eq!(stored_spawns(file_access, &settings), ["A", "C"]);
+        assert_eq!(file_access.read_seeds(&settings, 1).unwrap().count(), 1);
+
+        file_access.clean_seeds(&settings).unwrap();
+        file_access.write_seed(&seed("D"), &settings, 0).unwrap();
+        assert_eq!(stored_spawns(file_access, &settings), ["D"]);
+        assert_eq!(stored_spawns(file_access, &other_settings), ["B"]);
+
+        file_access.clean_all_seeds().unwrap();
+        assert!(stored_spawns(file_access, &other_settings).is_empty());
+    }
+
+    #[test]
+    fn settings_hash() {
+        let mut settings = UniverseSettings::default();
+        settings.world_settings.clear();
+        // FNV-1a over the eight bytes of the serialized length
+        assert_eq!(hash_settings(&settings), 0xa8c7_f832_281a_39c5);
+
+        let other_settings = UniverseSettings {
+            seed: "other".to_string(),
+            ..UniverseSettings::default()
+        };
+        assert_eq!(
+            hash_settings(&UniverseSettings::default()),
+            hash_settings(&other_settings)
+        );
+    }
+
+    #[test]
+    fn memory_storage() {
+        check_storage(&MemoryAccess::default());
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn archive_storage() {
+        struct RemoveOnDrop(std::path::PathBuf);
+        impl Drop for RemoveOnDrop {
+            fn drop(&mut self) {
+                let _ = std::fs::remove_file(&self.0);
+            }
+        }
+
+        let mut path = std::env::temp_dir();
+        path.push(format!("wotw_seedgen_stats_{}.archive", std::process::id()));
+        let _archive = RemoveOnDrop(path.clone());
+        check_storage(&ArchiveAccess::new(path));
+    }
+}
@@ -1,8 +1,11 @@
 pub mod analyzers;
+mod comparison;
 pub mod files;
 mod handle_errors;
 mod seed_storage;
 
+pub use comparison::{compare, ChiSquared, CompareArgs, CompareSide, Comparison};
+
 use std::{cmp::Ordering, fmt::Write, sync::Arc, time::Instant};
 
 use analyzers::Analyzer;
@@ -62,16 +65,7 @@ impl Stats {
         csv.push_str(", Count\n");
 
         let mut data = self.data.iter().collect::<Vec<_>>();
-        data.sort_unstable_by(|(a, _), (b, _)| {
-            for ((x, y), analyzer) in a.iter().zip(b.iter()).zip(self.analyzers.iter()) {
-                match analyzer.compare_keys()(x, y) {
-                    Ordering::Equal => (),
-                    non_eq => return non_eq,
-                }
-            }
-
-            Ordering::Equal
-        });
+        data.sort_unstable_by(|(a, _), (b, _)| compare_key_lists(&self.analyzers, a, b));
 
         csv.extend(Itertools::intersperse_with(
             data.into_iter().map(|(keys, value)| {
@@ -86,10 +80,28 @@ impl Stats {
     }
 }
 
+/// Orders lists of keys by the [`Analyzer::compare_keys`] of the [`Analyzer`] that created each key
+fn compare_key_lists(
+    analyzers: &ChainedAnalyzers,
+    a: &[Arc<String>],
+    b: &[Arc<String>],
+) -> Ordering {
+    for ((x, y), analyzer) in a.iter().zip(b.iter()).zip(analyzers.iter()) {
+        match analyzer.compare_keys()(x, y) {
+            Ordering::Equal => (),
+            non_eq => return non_eq,
+        }
+    }
+
+    Ordering::Equal
+}
+
 /// Generates a set of stats
 ///
 /// See [`StatsArgs`] for more details on the passed arguments
-pub fn stats<F: FileAccess>(args: StatsArgs) -> Result<Vec<Stats>> {
+///
+/// Seeds from previous runs will be reused and newly generated seeds stored through the [`FileAccess`]
+pub fn stats(args: StatsArgs, file_access: &impl FileAccess) -> Result<Vec<Stats>> {
     let now = Instant::now();
 
     let StatsArgs {
@@ -103,7 +115,7 @@ pub fn stats<F: FileAccess>(args: StatsArgs) -> Result<Vec<Stats>> {
     } = args;
 
     if overwrite_seed_storage {
-        F::clean_seeds(&settings)?;
+        file_access.clean_seeds(&settings)?;
         eprintln!("Cleaned seed storage for these settings");
     }
 
@@ -111,7 +123,8 @@ pub fn stats<F: FileAccess>(args: StatsArgs) -> Result<Vec<Stats>> {
         return Err("Multiworld seeds aren't well supported yet".to_string());
     }
 
-    let data = seed_storage::analyze::<F>(
+    let data = seed_storage::analyze(
+        file_access,
         &analyzers,
         &settings,
         sample_size,
@@ -22,7 +22,8 @@ const DEFAULT_ERROR_MESSAGE_LIMIT: usize = 10;
 const ANOTHER_THREAD_PANICKED: &str = "Another thread panicked";
 
 pub(crate) type SeedData = FxHashMap<Vec<Arc<String>>, u32>;
-pub(crate) fn analyze<F: FileAccess>(
+pub(crate) fn analyze(
+    file_access: &impl FileAccess,
     analyzers: &[ChainedAnalyzers],
     settings: &UniverseSettings,
     sample_size: usize,
@@ -34,7 +35,8 @@ pub(crate) fn analyze<F: FileAccess>(
         .take(analyzers.len())
         .collect::<Vec<_>>();
 
-    let existing_amount = analyze_existing_seeds::<F>(analyzers, settings, sample_size, &mut data)?;
+    let existing_amount =
+        analyze_existing_seeds(file_access, analyzers, settings, sample_size, &mut data)?;
 
     let missing = sample_size.saturating_sub(existing_amount);
     if missing > 0 {
@@ -83,7 +85,7 @@ pub(crate) fn analyze<F: FileAccess>(
                                     let mut error_messages_lock = error_messages.lock().expect(ANOTHER_THREAD_PANICKED);
 
                                     if error_messages_lock.len() < error_message_limit {
-                                        error_messages_lock.push(err);
+                                        error_messages_lock.push(err.to_string());
                                     }
                                     let errors = errors.fetch_add(1, Ordering::Relaxed) + 1;
                                     if errors > tolerated_errors {
@@ -102,7 +104,7 @@ pub(crate) fn analyze<F: FileAccess>(
                         };
 
                         if let Err(err) =
-                            F::write_seed(&seed, &settings, existing_amount + count)
+                            file_access.write_seed(&seed, &settings, existing_amount + count)
                         {
                             let write_errors = write_errors.fetch_add(1, Ordering::Relaxed);
                             if write_errors < 10 {
@@ -130,13 +132,14 @@ pub(crate) fn analyze<F: FileAccess>(
     }
 }
 
-fn analyze_existing_seeds<F: FileAccess>(
+fn analyze_existing_seeds(
+    file_access: &impl FileAccess,
     analyzers: &[ChainedAnalyzers],
     settings: &UniverseSettings,
     sample_size: usize,
     data: &mut [SeedData],
 ) -> Result<usize> {
-    let mut existing = HandleErrors::new(F::read_seeds(settings, sample_size)?, |err| {
+    let mut existing = HandleErrors::new(file_access.read_seeds(settings, sample_size)?, |err| {
         eprintln!("{err}");
     });
     let mut existing_amount = 0;